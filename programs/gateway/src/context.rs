@@ -1,7 +1,7 @@
 use anchor_lang::{prelude::*, solana_program};
-use anchor_spl::{associated_token::AssociatedToken, token::{self, Mint, Token, TokenAccount}};
+use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}, token_2022::Token2022};
 
-use crate::state::Order;
+use crate::state::{Bid, Bond, Channel, ClientStats, CreditAccount, JobRequest, MintConfig, Order, Provider, ProviderProfile, Reputation, Subscription};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -21,9 +21,13 @@ pub struct Initialize<'info> {
 #[derive(Accounts)]
 #[instruction(job_hash: [u8; 32])]
 pub struct Commit<'info> {
+    /// Pays for the order and gets refunds, may be a PDA signing through CPI
     #[account(mut)]
     pub user: Signer<'info>,
 
+    /// CHECK: The order is placed on behalf of this account, may be `user` itself
+    pub beneficiary: UncheckedAccount<'info>,
+
     /// CHECK: This is a sysvar account that contains the instructions
     #[account(
         address = solana_program::sysvar::instructions::ID
@@ -34,15 +38,15 @@ pub struct Commit<'info> {
         init,
         payer = user,
         space = Order::SIZE,
-        seeds = [b"order", user.key().as_ref(), job_hash.as_ref()],
+        seeds = [b"order", beneficiary.key().as_ref(), job_hash.as_ref()],
         bump
     )]
     pub order: Account<'info, crate::state::Order>,
     
     #[account(
         mut,
-        associated_token::authority = user,
-        associated_token::mint = mint
+        token::mint = mint,
+        token::authority = user
     )]
     pub user_token_account: Account<'info, TokenAccount>,
     
@@ -56,12 +60,29 @@ pub struct Commit<'info> {
     )]
     pub order_vault_token_account: Account<'info, TokenAccount>,
     
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", beneficiary.key().as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
     pub mint: Account<'info, Mint>,
     
     /// CHECK: This is a PDA that will be used as the token account authority
     //#[account(seeds = [b"vault-authority"], bump)]
     //pub vault_authority: AccountInfo<'info>,
 
+    /// CHECK: The provider's bond, empty if it never deposited one, see `lock_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", config.authority.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+
     #[account(
         seeds = [b"config"],
         bump
@@ -75,9 +96,65 @@ pub struct Commit<'info> {
 
 #[derive(Accounts)]
 pub struct Deliver<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = order.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
+    pub order: Account<'info, crate::state::Order>,
     #[account(mut)]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
+    // Receives the late-delivery penalty
+    #[account(
+        mut,
+        token::mint = order.mint,
+        token::authority = order.payer
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct DeliverEncrypted<'info> {
     pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = order.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
+    pub order: Account<'info, crate::state::Order>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct AcknowledgeCiphertext<'info> {
+    pub user: Signer<'info>,
+    #[account(mut, has_one = user @ crate::errors::ErrorCode::InvalidUser)]
+    pub order: Account<'info, crate::state::Order>,
+}
+
+#[derive(Accounts)]
+pub struct RevealKey<'info> {
     #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Receives the order vault rent, must be the order's payer
+    #[account(mut, address = order.payer @ crate::errors::ErrorCode::InvalidUser)]
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint,
+        constraint = order.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
     pub order: Account<'info, crate::state::Order>,
     #[account(mut)]
     pub order_vault_token_account: Account<'info, TokenAccount>,
@@ -85,10 +162,18 @@ pub struct Deliver<'info> {
     #[account(seeds = [b"vault-authority"], bump)]
     pub vault_authority: AccountInfo<'info>,
 
+    // Receives the late-delivery penalty and a forfeited bonus
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
     #[account(
         init_if_needed,
         payer = authority,
-        seeds = [b"vault", mint.key().as_ref()],
+        seeds = [b"vault", authority.key().as_ref(), mint.key().as_ref()],
         bump,
         token::mint = mint,
         token::authority = vault_authority,
@@ -96,10 +181,32 @@ pub struct Deliver<'info> {
     pub vault_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        seeds = [b"config"],
-        has_one = authority,
+        init_if_needed,
+        payer = authority,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", order.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    /// CHECK: The provider's bond, empty if it never deposited one, see `release_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", authority.key().as_ref(), mint.key().as_ref()],
         bump
     )]
+    pub bond: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump)]
     pub config: Account<'info, crate::state::Config>,
 
     pub mint: Account<'info, Mint>,
@@ -109,31 +216,68 @@ pub struct Deliver<'info> {
 }
 
 #[derive(Accounts)]
-pub struct Decline<'info> {
+pub struct Finalize<'info> {
+    /// Anyone may finalize once the challenge window is over or a vesting order has fully vested
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub payer: Signer<'info>,
 
-    /// CHECK: This is a user account that will be used as the token account authority
+    /// CHECK: Receives the order vault rent, must be the order's payer
+    #[account(mut, address = order.payer @ crate::errors::ErrorCode::InvalidUser)]
     pub user: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    pub order: Account<'info, crate::state::Order>,
-    
+    // Receives the client deposit
     #[account(
         mut,
-        associated_token::authority = user,
-        associated_token::mint = mint
+        token::mint = mint,
+        token::authority = user
     )]
     pub user_token_account: Account<'info, TokenAccount>,
-    
+
+    #[account(mut, has_one = mint @ crate::errors::ErrorCode::InvalidMint)]
+    pub order: Account<'info, crate::state::Order>,
     #[account(mut)]
     pub order_vault_token_account: Account<'info, TokenAccount>,
-    
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
     #[account(
-        seeds = [b"config"],
-        has_one = authority,
+        init_if_needed,
+        payer = payer,
+        seeds = [b"vault", order.provider.as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", order.provider.as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", order.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    /// CHECK: The provider's bond, empty if it never deposited one, see `release_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", order.provider.as_ref(), mint.key().as_ref()],
         bump
     )]
+    pub bond: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump)]
     pub config: Account<'info, crate::state::Config>,
 
     pub mint: Account<'info, Mint>,
@@ -143,23 +287,76 @@ pub struct Decline<'info> {
 }
 
 #[derive(Accounts)]
-pub struct Refund<'info> {
+pub struct AcceptDelivery<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
-    #[account(mut)]
-    pub order: Account<'info, crate::state::Order>,
-    
+    /// CHECK: Receives the order vault rent, must be the order's payer
+    #[account(mut, address = order.payer @ crate::errors::ErrorCode::InvalidUser)]
+    pub payer: UncheckedAccount<'info>,
+
+    // Receives the client deposit
     #[account(
         mut,
-        associated_token::authority = user,
-        associated_token::mint = mint
+        token::mint = mint,
+        token::authority = payer
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = user @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub order: Account<'info, crate::state::Order>,
     #[account(mut)]
     pub order_vault_token_account: Account<'info, TokenAccount>,
-    
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"vault", order.provider.as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", order.provider.as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", order.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    #[account(seeds = [b"mint-config", mint.key().as_ref()], bump)]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+
+    /// CHECK: The provider's bond, empty if it never deposited one, see `release_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", order.provider.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
     pub mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -167,34 +364,1391 @@ pub struct Refund<'info> {
 }
 
 #[derive(Accounts)]
-pub struct Withdraw<'info> {
+pub struct Dispute<'info> {
     #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, has_one = user @ crate::errors::ErrorCode::InvalidUser)]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", order.provider.as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", order.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
     pub authority: Signer<'info>,
-    
+
     #[account(
+        mut,
         seeds = [b"config"],
         has_one = authority,
         bump
     )]
     pub config: Account<'info, crate::state::Config>,
-    
+}
+
+#[derive(Accounts)]
+pub struct Decline<'info> {
     #[account(mut)]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+
+    /// CHECK: The order's payer, used as the refund token account authority
+    #[account(address = order.payer @ crate::errors::ErrorCode::InvalidUser)]
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = order.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
+    pub order: Account<'info, crate::state::Order>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
     
+    #[account(mut)]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
     #[account(
         init_if_needed,
         payer = authority,
-        associated_token::authority = authority,
-        associated_token::mint = mint
+        space = Reputation::SIZE,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump
     )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
-    
-    /// CHECK: This is a PDA that will be used as the token account authority
-    #[account(seeds = [b"vault-authority"], bump)]
-    pub vault_authority: AccountInfo<'info>,
-    
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(
+        mut,
+        seeds = [b"bond", order.provider.as_ref(), order.mint.as_ref()],
+        bump
+    )]
+    pub bond: Option<Account<'info, crate::state::Bond>>,
+
+    #[account(
+        mut,
+        seeds = [b"bond-vault", order.provider.as_ref(), order.mint.as_ref()],
+        bump
+    )]
+    pub bond_vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", order.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
     pub mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-} 
\ No newline at end of file
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, constraint = order.payer == user.key() @ crate::errors::ErrorCode::InvalidUser)]
+    pub order: Account<'info, crate::state::Order>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", order.provider.as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", order.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond", order.provider.as_ref(), order.mint.as_ref()],
+        bump
+    )]
+    pub bond: Option<Account<'info, crate::state::Bond>>,
+
+    #[account(
+        mut,
+        seeds = [b"bond-vault", order.provider.as_ref(), order.mint.as_ref()],
+        bump
+    )]
+    pub bond_vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::authority = authority,
+        associated_token::mint = mint
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+} 
+#[derive(Accounts)]
+#[instruction(job_hash: [u8; 32])]
+pub struct Estimate<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Order::SIZE,
+        seeds = [b"order", user.key().as_ref(), job_hash.as_ref()],
+        bump
+    )]
+    pub order: Account<'info, crate::state::Order>,
+
+    pub mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Evaluate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        seeds = [b"config"],
+        has_one = authority,
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+}
+
+#[derive(Accounts)]
+pub struct CommitEvaluation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = user @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint,
+        seeds = [b"order", user.key().as_ref(), order.job_hash.as_ref()],
+        bump
+    )]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"vault", order.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = order
+    )]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", user.key().as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    /// CHECK: The provider's bond, empty if it never deposited one, see `lock_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", order.provider.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct RegisterProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Provider::SIZE,
+        seeds = [b"provider", authority.key().as_ref()],
+        bump
+    )]
+    pub provider: Account<'info, crate::state::Provider>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+#[instruction(job_hash: [u8; 32])]
+pub struct OpenJobRequest<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = JobRequest::SIZE,
+        seeds = [b"job-request", user.key().as_ref(), job_hash.as_ref()],
+        bump
+    )]
+    pub job_request: Account<'info, crate::state::JobRequest>,
+
+    pub mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"provider", authority.key().as_ref()],
+        has_one = authority,
+        bump
+    )]
+    pub provider: Account<'info, crate::state::Provider>,
+
+    #[account(
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(mut)]
+    pub job_request: Account<'info, crate::state::JobRequest>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Bid::SIZE,
+        seeds = [b"bid", job_request.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, crate::state::Bid>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct AcceptBid<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = user @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub job_request: Account<'info, crate::state::JobRequest>,
+
+    #[account(has_one = job_request)]
+    pub bid: Account<'info, crate::state::Bid>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Order::SIZE,
+        seeds = [b"order", user.key().as_ref(), job_request.job_hash.as_ref()],
+        bump
+    )]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"vault", order.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = order
+    )]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    /// CHECK: The provider's bond, empty if it never deposited one, see `lock_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", bid.provider.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", job_request.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct TopUpCredit<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// CHECK: The provider the credit can be drawn by, only used as a seed
+    pub provider: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = client,
+        space = CreditAccount::SIZE,
+        seeds = [b"credit", client.key().as_ref(), provider.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub credit: Account<'info, crate::state::CreditAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = client,
+        seeds = [b"credit-vault", credit.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = credit
+    )]
+    pub credit_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = client,
+        associated_token::mint = mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DrawCredit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is a sysvar account that contains the instructions
+    #[account(
+        address = solana_program::sysvar::instructions::ID
+    )]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint,
+        constraint = credit.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
+    pub credit: Account<'info, crate::state::CreditAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"credit-vault", credit.key().as_ref()],
+        bump
+    )]
+    pub credit_vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestCreditWithdrawal<'info> {
+    pub client: Signer<'info>,
+
+    #[account(mut, has_one = client @ crate::errors::ErrorCode::InvalidUser)]
+    pub credit: Account<'info, crate::state::CreditAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCredit<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = client @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub credit: Account<'info, crate::state::CreditAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"credit-vault", credit.key().as_ref()],
+        bump
+    )]
+    pub credit_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = client,
+        associated_token::mint = mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+#[instruction(channel_id: u64)]
+pub struct OpenChannel<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// CHECK: The provider allowed to close the channel, only used as a seed
+    pub provider: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = client,
+        space = Channel::SIZE,
+        seeds = [b"channel", client.key().as_ref(), provider.key().as_ref(), channel_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub channel: Account<'info, crate::state::Channel>,
+
+    #[account(
+        init,
+        payer = client,
+        seeds = [b"channel-vault", channel.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = channel
+    )]
+    pub channel_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = client,
+        associated_token::mint = mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct CloseChannel<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is a sysvar account that contains the instructions
+    #[account(
+        address = solana_program::sysvar::instructions::ID
+    )]
+    pub instructions: AccountInfo<'info>,
+
+    /// CHECK: Receives the channel vault rent, must be the channel's client
+    #[account(mut, address = channel.client @ crate::errors::ErrorCode::InvalidUser)]
+    pub client: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint,
+        constraint = channel.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
+    pub channel: Account<'info, crate::state::Channel>,
+
+    #[account(
+        mut,
+        seeds = [b"channel-vault", channel.key().as_ref()],
+        bump
+    )]
+    pub channel_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = client,
+        associated_token::mint = mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct ForceCloseChannel<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = client @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub channel: Account<'info, crate::state::Channel>,
+
+    #[account(
+        mut,
+        seeds = [b"channel-vault", channel.key().as_ref()],
+        bump
+    )]
+    pub channel_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = client,
+        associated_token::mint = mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+#[instruction(subscription_id: u64)]
+pub struct CreateSubscription<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// CHECK: The provider allowed to charge the subscription, only used as a seed
+    pub provider: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = client,
+        space = Subscription::SIZE,
+        seeds = [b"subscription", client.key().as_ref(), provider.key().as_ref(), subscription_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, crate::state::Subscription>,
+
+    #[account(
+        init,
+        payer = client,
+        seeds = [b"subscription-vault", subscription.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = subscription
+    )]
+    pub subscription_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = client,
+        associated_token::mint = mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct Charge<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint,
+        constraint = subscription.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
+    pub subscription: Account<'info, crate::state::Subscription>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription-vault", subscription.key().as_ref()],
+        bump
+    )]
+    pub subscription_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = client_token_account.owner == subscription.client @ crate::errors::ErrorCode::InvalidTokenAccountOwner
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    pub client: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = client @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub subscription: Account<'info, crate::state::Subscription>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription-vault", subscription.key().as_ref()],
+        bump
+    )]
+    pub subscription_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = client,
+        associated_token::mint = mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint,
+        constraint = order.provider == authority.key() @ crate::errors::ErrorCode::InvalidProvider
+    )]
+    pub order: Account<'info, crate::state::Order>,
+    #[account(mut)]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct StopVesting<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Gets the unvested remainder and the order vault rent
+    #[account(mut, address = order.payer @ crate::errors::ErrorCode::InvalidUser)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = user @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        mut,
+        associated_token::authority = payer,
+        associated_token::mint = mint
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"vault", order.provider.as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"bond", order.provider.as_ref(), order.mint.as_ref()],
+        bump
+    )]
+    pub bond: Option<Account<'info, crate::state::Bond>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", order.user.as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct Tip<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = user @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"vault", order.provider.as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", order.provider.as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AmendOrder<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: This is a sysvar account that contains the instructions
+    #[account(
+        address = solana_program::sysvar::instructions::ID
+    )]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = order.payer == user.key() @ crate::errors::ErrorCode::InvalidUser,
+        has_one = mint @ crate::errors::ErrorCode::InvalidMint
+    )]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", order.key().as_ref()],
+        bump
+    )]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The provider's bond, empty if it never deposited one, see `relock_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", order.provider.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct RequestRevision<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut, has_one = user @ crate::errors::ErrorCode::InvalidUser)]
+    pub order: Account<'info, crate::state::Order>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+#[instruction(job_hash: [u8; 32])]
+pub struct CommitRelayed<'info> {
+    /// Submits the transaction and fronts the rent
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Authorizes the commit with an ed25519 signature instead of signing the transaction
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: This is a sysvar account that contains the instructions
+    #[account(
+        address = solana_program::sysvar::instructions::ID
+    )]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = Order::SIZE,
+        seeds = [b"order", user.key().as_ref(), job_hash.as_ref()],
+        bump
+    )]
+    pub order: Account<'info, crate::state::Order>,
+
+    // Must have approved `relay_authority` as delegate
+    #[account(
+        mut,
+        associated_token::authority = user,
+        associated_token::mint = mint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = relayer
+    )]
+    pub relayer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = relayer,
+        seeds = [b"vault", order.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = order
+    )]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the token delegate for relayed commits
+    #[account(seeds = [b"relay-authority"], bump)]
+    pub relay_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = ClientStats::SIZE,
+        seeds = [b"client-stats", user.key().as_ref()],
+        bump
+    )]
+    pub client_stats: Account<'info, crate::state::ClientStats>,
+
+    /// CHECK: The provider's bond, empty if it never deposited one, see `lock_bond`
+    #[account(
+        mut,
+        seeds = [b"bond", config.authority.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct DepositBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Bond::SIZE,
+        seeds = [b"bond", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: Account<'info, crate::state::Bond>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"bond-vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = bond,
+    )]
+    pub bond_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = authority,
+        associated_token::mint = mint
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Reputation::SIZE,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bond", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond: Account<'info, crate::state::Bond>,
+
+    #[account(
+        mut,
+        seeds = [b"bond-vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub bond_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = authority,
+        associated_token::mint = mint
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, crate::state::Reputation>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        has_one = authority,
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MintConfig::SIZE,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetReputation<'info> {
+    /// CHECK: Only used to derive the reputation address
+    pub provider: UncheckedAccount<'info>,
+
+    /// CHECK: The provider's reputation, empty until its first order settles
+    #[account(
+        seeds = [b"reputation", provider.key().as_ref()],
+        bump
+    )]
+    pub reputation: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+}
+
+#[derive(Accounts)]
+pub struct MintReceipt<'info> {
+    /// The order's client or the payer who funded it
+    #[account(
+        mut,
+        constraint = payer.key() == order.user || payer.key() == order.payer @ crate::errors::ErrorCode::InvalidUser
+    )]
+    pub payer: Signer<'info>,
+
+    pub order: Account<'info, crate::state::Order>,
+
+    /// CHECK: Created here as a Token-2022 mint, one per order
+    #[account(
+        mut,
+        seeds = [b"receipt", order.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The order's client
+    #[account(address = order.user @ crate::errors::ErrorCode::InvalidUser)]
+    pub client: UncheckedAccount<'info>,
+    /// CHECK: Created here as the client's associated token account, checked by that program
+    #[account(mut)]
+    pub client_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: The order's provider
+    #[account(address = order.provider @ crate::errors::ErrorCode::InvalidProvider)]
+    pub provider: UncheckedAccount<'info>,
+    /// CHECK: Created here as the provider's associated token account, checked by that program
+    #[account(mut)]
+    pub provider_receipt_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProfile<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProviderProfile::SIZE,
+        seeds = [b"profile", authority.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, ProviderProfile>,
+
+    // Absent for the deployment authority, which signs with `Config::authority_signer`
+    #[account(seeds = [b"provider", authority.key().as_ref()], bump)]
+    pub provider: Option<Account<'info, crate::state::Provider>>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Config in the single-provider layout, checked in `process_migrate_config`
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateOrder<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Order in the single-provider layout, checked in `process_migrate_order`
+    #[account(mut)]
+    pub order: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"vault", order.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = order
+    )]
+    pub order_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, crate::state::Config>,
+
+    pub mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepLegacyVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        has_one = authority,
+        bump
+    )]
+    pub config: Account<'info, crate::state::Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority
+    )]
+    pub legacy_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"vault", authority.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that will be used as the token account authority
+    #[account(seeds = [b"vault-authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -36,4 +36,76 @@ pub enum ErrorCode {
     DeliverAfterDeadline,
     #[msg("Refund before deadline")]
     RefundBeforeDeadline,
+    #[msg("Price exceeds budget")]
+    BudgetExceeded,
+    #[msg("Invalid provider")]
+    InvalidProvider,
+    #[msg("Bidding is closed")]
+    BiddingClosed,
+    #[msg("Invalid deadline")]
+    InvalidDeadline,
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Challenge window is closed")]
+    ChallengeWindowClosed,
+    #[msg("Invalid rating")]
+    InvalidRating,
+    #[msg("Decryption key does not match the committed key hash")]
+    KeyHashMismatch,
+    #[msg("Stale usage receipt")]
+    StaleReceipt,
+    #[msg("Withdrawal not requested")]
+    WithdrawalNotRequested,
+    #[msg("Notice period not over")]
+    NoticePeriodNotOver,
+    #[msg("Channel is closed")]
+    ChannelClosed,
+    #[msg("Channel has not expired")]
+    ChannelNotExpired,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Subscription period not over")]
+    PeriodNotOver,
+    #[msg("All subscription periods charged")]
+    SubscriptionFinished,
+    #[msg("Subscription is not active")]
+    SubscriptionNotActive,
+    #[msg("Order is not a vesting order")]
+    NotVestingOrder,
+    #[msg("Vesting orders cannot be refunded")]
+    VestingOrder,
+    #[msg("Amendment does not apply to this order")]
+    InvalidAmendment,
+    #[msg("No revision rounds left")]
+    RevisionLimitReached,
+    #[msg("Relay authorization does not match the offer")]
+    InvalidAuthorization,
+    #[msg("Bonded order requires the provider bond account")]
+    BondRequired,
+    #[msg("Provider bond does not cover more outstanding work")]
+    BondCapacityExceeded,
+    #[msg("Bond is backing outstanding work")]
+    BondLocked,
+    #[msg("Basis points above 10000")]
+    InvalidBasisPoints,
+    #[msg("Client reputation does not meet the offer's requirement")]
+    ClientReputationTooLow,
+    #[msg("Feedback unit must be positive")]
+    InvalidFeedbackUnit,
+    #[msg("Profile field too long")]
+    ProfileFieldTooLong,
+    #[msg("Token account is delegated to another flow")]
+    TokenAccountDelegated,
+    #[msg("Vesting order has not fully vested")]
+    VestingInProgress,
+    #[msg("Vesting order has fully vested, finalize it instead")]
+    VestingComplete,
+    #[msg("Offer layout version is not supported")]
+    UnsupportedOfferVersion,
+    #[msg("Account is not a legacy account of this program")]
+    InvalidLegacyAccount,
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
+    #[msg("Challengeable deliveries need a dispute period")]
+    DisputePeriodRequired,
 } 
\ No newline at end of file
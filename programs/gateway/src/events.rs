@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{OrderStatus, ScoreParams};
 
 #[event]
 pub struct OrderPlaced {
@@ -11,6 +12,8 @@ pub struct OrderPlaced {
 pub struct EvaluationMade {
     pub order: Pubkey,
     pub price: u64,
+    pub price_valid_until: u64,
+    pub deadline: i64,
 }
 
 #[event]
@@ -32,4 +35,280 @@ pub struct Abort {
 #[event]
 pub struct RefundEvent {
     pub order: Pubkey,
-} 
\ No newline at end of file
+}
+
+#[event]
+pub struct ProviderRegistered {
+    pub provider: Pubkey,
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct JobRequestOpened {
+    pub job_request: Pubkey,
+    pub user: Pubkey,
+    pub job_hash: [u8; 32],
+    pub budget: u64,
+    pub bidding_deadline: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub job_request: Pubkey,
+    pub bid: Pubkey,
+    pub provider: Pubkey,
+    pub price: u64,
+    pub deadline: i64,
+    pub orders_completed: u64,
+    pub orders_aborted: u64,
+    pub orders_refunded: u64,
+    pub volume: u64,
+}
+
+#[event]
+pub struct BidAccepted {
+    pub job_request: Pubkey,
+    pub bid: Pubkey,
+    pub order: Pubkey,
+}
+
+#[event]
+pub struct Finalized {
+    pub order: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub order: Pubkey,
+    pub ends_at: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub challenge_window: i64,
+    pub credit_notice_period: i64,
+    pub max_revisions: u8,
+    pub revision_period: i64,
+    pub bond_slash_bps: u16,
+    pub bond_leverage: u16,
+    pub score: ScoreParams,
+    pub dispute_period: i64,
+}
+
+#[event]
+pub struct DeliveryAccepted {
+    pub order: Pubkey,
+    pub amount: u64,
+    pub rating: Option<u8>,
+    // Feedback weight of the rating, 0 when it does not count
+    pub weight: u64,
+}
+
+#[event]
+pub struct CiphertextCommitted {
+    pub order: Pubkey,
+    pub ciphertext_hash: [u8; 32],
+    pub key_hash: [u8; 32],
+}
+
+#[event]
+pub struct CiphertextAcknowledged {
+    pub order: Pubkey,
+}
+
+#[event]
+pub struct KeyRevealed {
+    pub order: Pubkey,
+    pub decryption_key: [u8; 32],
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreditToppedUp {
+    pub credit: Pubkey,
+    pub amount: u64,
+    pub deposited: u64,
+}
+
+#[event]
+pub struct CreditDrawn {
+    pub credit: Pubkey,
+    pub amount: u64,
+    pub cumulative_amount: u64,
+    pub counter: u64,
+}
+
+#[event]
+pub struct CreditWithdrawalRequested {
+    pub credit: Pubkey,
+    pub available_at: i64,
+}
+
+#[event]
+pub struct CreditWithdrawn {
+    pub credit: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ChannelOpened {
+    pub channel: Pubkey,
+    pub client: Pubkey,
+    pub provider: Pubkey,
+    pub deposit: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ChannelClosed {
+    pub channel: Pubkey,
+    pub amount: u64,
+    pub refunded: u64,
+    pub duration: i64,
+}
+
+#[event]
+pub struct ChannelForceClosed {
+    pub channel: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct SubscriptionCreated {
+    pub subscription: Pubkey,
+    pub client: Pubkey,
+    pub provider: Pubkey,
+    pub period: i64,
+    pub price_per_period: u64,
+    pub periods: u32,
+}
+
+#[event]
+pub struct SubscriptionCharged {
+    pub subscription: Pubkey,
+    pub period_index: u32,
+    pub result_hash: [u8; 32],
+    pub amount: u64,
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub subscription: Pubkey,
+    pub periods: u32,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub order: Pubkey,
+    pub amount: u64,
+    pub vested_claimed: u64,
+}
+
+#[event]
+pub struct VestingStopped {
+    pub order: Pubkey,
+    pub paid: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct LateDelivery {
+    pub order: Pubkey,
+    pub late_by: i64,
+    pub penalty: u64,
+}
+
+#[event]
+pub struct BonusSettled {
+    pub order: Pubkey,
+    pub bonus: u64,
+    pub earned: bool,
+}
+
+#[event]
+pub struct Tipped {
+    pub order: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OrderAmended {
+    pub order: Pubkey,
+    pub amendment: u32,
+    pub old_price: u64,
+    pub new_price: u64,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+}
+
+#[event]
+pub struct RevisionRequested {
+    pub order: Pubkey,
+    pub revision: u8,
+    pub revision_deadline: i64,
+}
+
+#[event]
+pub struct OrderRelayed {
+    pub order: Pubkey,
+    pub relayer: Pubkey,
+    pub relayer_fee: u64,
+}
+
+#[event]
+pub struct BondDeposited {
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+}
+
+#[event]
+pub struct BondWithdrawn {
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+}
+
+#[event]
+pub struct BondSlashed {
+    pub order: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DepositSettled {
+    pub order: Pubkey,
+    pub amount: u64,
+    pub forfeited: bool,
+}
+
+#[event]
+pub struct MintConfigUpdated {
+    pub mint: Pubkey,
+    pub unit: u64,
+    pub min_feedback_price: u64,
+}
+
+#[event]
+pub struct ReceiptMinted {
+    pub order: Pubkey,
+    pub mint: Pubkey,
+    pub outcome: OrderStatus,
+}
+
+#[event]
+pub struct ProfileUpdated {
+    pub provider: Pubkey,
+    pub signer: Pubkey,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub metadata_uri: String,
+    pub quote_endpoint: String,
+    pub accepted_mints: Vec<Pubkey>,
+}
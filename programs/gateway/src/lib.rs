@@ -9,6 +9,7 @@ pub mod events;
 pub mod utils;
 
 use crate::context::*;
+use crate::state::{ConfigParams, ProfileParams, ReputationView, SubscriptionFunding};
 #[program]
 pub mod gateway {
     use super::*;
@@ -37,4 +38,193 @@ pub mod gateway {
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         processor::withdraw::process_withdraw(ctx)
     }
-}
\ No newline at end of file
+
+    pub fn estimate(ctx: Context<Estimate>, job_hash: [u8; 32], budget: u64) -> Result<()> {
+        processor::estimate::process_estimate(ctx, job_hash, budget)
+    }
+
+    pub fn evaluate(
+        ctx: Context<Evaluate>,
+        price: u64,
+        price_valid_until: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        processor::evaluate::process_evaluate(ctx, price, price_valid_until, deadline)
+    }
+
+    pub fn commit_evaluation(ctx: Context<CommitEvaluation>) -> Result<()> {
+        processor::commit_evaluation::process_commit_evaluation(ctx)
+    }
+
+    pub fn register_provider(ctx: Context<RegisterProvider>, signer: Pubkey) -> Result<()> {
+        processor::register_provider::process_register_provider(ctx, signer)
+    }
+
+    pub fn open_job_request(
+        ctx: Context<OpenJobRequest>,
+        job_hash: [u8; 32],
+        budget: u64,
+        bidding_deadline: i64,
+    ) -> Result<()> {
+        processor::open_job_request::process_open_job_request(ctx, job_hash, budget, bidding_deadline)
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, price: u64, deadline: i64) -> Result<()> {
+        processor::place_bid::process_place_bid(ctx, price, deadline)
+    }
+
+    pub fn accept_bid(ctx: Context<AcceptBid>) -> Result<()> {
+        processor::accept_bid::process_accept_bid(ctx)
+    }
+
+    pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
+        processor::finalize::process_finalize(ctx)
+    }
+
+    pub fn dispute(ctx: Context<Dispute>) -> Result<()> {
+        processor::dispute::process_dispute(ctx)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        processor::update_config::process_update_config(ctx, params)
+    }
+
+    pub fn accept_delivery(ctx: Context<AcceptDelivery>, rating: Option<u8>) -> Result<()> {
+        processor::accept_delivery::process_accept_delivery(ctx, rating)
+    }
+
+    pub fn deliver_encrypted(
+        ctx: Context<DeliverEncrypted>,
+        ciphertext_hash: [u8; 32],
+        key_hash: [u8; 32],
+    ) -> Result<()> {
+        processor::deliver_encrypted::process_deliver_encrypted(ctx, ciphertext_hash, key_hash)
+    }
+
+    pub fn acknowledge_ciphertext(ctx: Context<AcknowledgeCiphertext>) -> Result<()> {
+        processor::acknowledge_ciphertext::process_acknowledge_ciphertext(ctx)
+    }
+
+    pub fn reveal_key(ctx: Context<RevealKey>, decryption_key: [u8; 32]) -> Result<()> {
+        processor::reveal_key::process_reveal_key(ctx, decryption_key)
+    }
+
+    pub fn top_up_credit(ctx: Context<TopUpCredit>, amount: u64) -> Result<()> {
+        processor::top_up_credit::process_top_up_credit(ctx, amount)
+    }
+
+    pub fn draw_credit(ctx: Context<DrawCredit>) -> Result<()> {
+        processor::draw_credit::process_draw_credit(ctx)
+    }
+
+    pub fn request_credit_withdrawal(ctx: Context<RequestCreditWithdrawal>) -> Result<()> {
+        processor::request_credit_withdrawal::process_request_credit_withdrawal(ctx)
+    }
+
+    pub fn withdraw_credit(ctx: Context<WithdrawCredit>) -> Result<()> {
+        processor::withdraw_credit::process_withdraw_credit(ctx)
+    }
+
+    pub fn open_channel(
+        ctx: Context<OpenChannel>,
+        channel_id: u64,
+        amount: u64,
+        timeout: i64,
+    ) -> Result<()> {
+        processor::open_channel::process_open_channel(ctx, channel_id, amount, timeout)
+    }
+
+    pub fn close_channel(ctx: Context<CloseChannel>) -> Result<()> {
+        processor::close_channel::process_close_channel(ctx)
+    }
+
+    pub fn force_close_channel(ctx: Context<ForceCloseChannel>) -> Result<()> {
+        processor::force_close_channel::process_force_close_channel(ctx)
+    }
+
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        subscription_id: u64,
+        period: i64,
+        price_per_period: u64,
+        periods: u32,
+        funding: SubscriptionFunding,
+    ) -> Result<()> {
+        processor::create_subscription::process_create_subscription(
+            ctx,
+            subscription_id,
+            period,
+            price_per_period,
+            periods,
+            funding,
+        )
+    }
+
+    pub fn charge(ctx: Context<Charge>, result_hash: [u8; 32]) -> Result<()> {
+        processor::charge::process_charge(ctx, result_hash)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        processor::cancel_subscription::process_cancel_subscription(ctx)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        processor::claim_vested::process_claim_vested(ctx)
+    }
+
+    pub fn stop_vesting(ctx: Context<StopVesting>) -> Result<()> {
+        processor::stop_vesting::process_stop_vesting(ctx)
+    }
+
+    pub fn tip(ctx: Context<Tip>, amount: u64) -> Result<()> {
+        processor::tip::process_tip(ctx, amount)
+    }
+
+    pub fn amend_order(ctx: Context<AmendOrder>) -> Result<()> {
+        processor::amend_order::process_amend_order(ctx)
+    }
+
+    pub fn request_revision(ctx: Context<RequestRevision>) -> Result<()> {
+        processor::request_revision::process_request_revision(ctx)
+    }
+
+    pub fn commit_relayed(ctx: Context<CommitRelayed>, job_hash: [u8; 32]) -> Result<()> {
+        processor::commit_relayed::process_commit_relayed(ctx, job_hash)
+    }
+
+    pub fn deposit_bond(ctx: Context<DepositBond>, amount: u64) -> Result<()> {
+        processor::deposit_bond::process_deposit_bond(ctx, amount)
+    }
+
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>, amount: u64) -> Result<()> {
+        processor::withdraw_bond::process_withdraw_bond(ctx, amount)
+    }
+
+    pub fn set_mint_config(ctx: Context<SetMintConfig>, unit: u64, min_feedback_price: u64) -> Result<()> {
+        processor::set_mint_config::process_set_mint_config(ctx, unit, min_feedback_price)
+    }
+
+    pub fn get_reputation(ctx: Context<GetReputation>) -> Result<ReputationView> {
+        processor::get_reputation::process_get_reputation(ctx)
+    }
+
+    pub fn mint_receipt(ctx: Context<MintReceipt>) -> Result<()> {
+        processor::mint_receipt::process_mint_receipt(ctx)
+    }
+
+    pub fn set_profile(ctx: Context<SetProfile>, params: ProfileParams) -> Result<()> {
+        processor::set_profile::process_set_profile(ctx, params)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        processor::migrate_config::process_migrate_config(ctx)
+    }
+
+    pub fn migrate_order(ctx: Context<MigrateOrder>) -> Result<()> {
+        processor::migrate_order::process_migrate_order(ctx)
+    }
+
+    pub fn sweep_legacy_vault(ctx: Context<SweepLegacyVault>) -> Result<()> {
+        processor::sweep_legacy_vault::process_sweep_legacy_vault(ctx)
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::AcceptBid;
+use crate::state::{JobRequestStatus, OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::{BidAccepted, Start};
+use crate::processor::deposit_bond::lock_bond;
+
+pub fn process_accept_bid(ctx: Context<AcceptBid>) -> Result<()> {
+    let now = ctx.accounts.clock.unix_timestamp;
+    let job_request = &mut ctx.accounts.job_request;
+    let bid = &ctx.accounts.bid;
+    require!(job_request.status == JobRequestStatus::Open, ErrorCode::BiddingClosed);
+    require!(now < bid.deadline, ErrorCode::InvalidDeadline);
+
+    job_request.status = JobRequestStatus::Awarded;
+
+    let order = &mut ctx.accounts.order;
+    order.user = job_request.user;
+    order.payer = job_request.user;
+    order.job_hash = job_request.job_hash;
+    order.mint = job_request.mint;
+    order.budget = job_request.budget;
+    order.provider = bid.provider;
+    order.signer = bid.signer;
+    order.price = bid.price;
+    order.price_valid_until = job_request.bidding_deadline as u64;
+    order.deadline = bid.deadline;
+    order.status = OrderStatus::Started;
+    order.started_at = now;
+
+    if !order.is_self_dealt() {
+        let client_stats = &mut ctx.accounts.client_stats;
+        client_stats.client = order.user;
+        client_stats.orders_placed += 1;
+    }
+    let config = &ctx.accounts.config;
+    lock_bond(order, &ctx.accounts.bond, config.bond_leverage, config.bond_slash_bps)?;
+
+    // Transfer tokens to vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.order_vault_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            }
+        ),
+        order.price
+    )?;
+
+    emit!(BidAccepted {
+        job_request: job_request.key(),
+        bid: bid.key(),
+        order: order.key(),
+    });
+
+    emit!(Start {
+        order: order.key(),
+    });
+
+    Ok(())
+}
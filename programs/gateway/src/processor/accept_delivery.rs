@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::context::AcceptDelivery;
+use crate::state::{MintConfig, OrderStatus, ScoreEvent, MAX_RATING, UNSATISFIED_RATING};
+use crate::errors::ErrorCode;
+use crate::events::DeliveryAccepted;
+use crate::processor::deposit_bond::release_bond;
+use crate::processor::finalize::{record_completion, release_escrow};
+
+// Also how a client concedes a dispute: accepting releases the escrow.
+pub fn process_accept_delivery(ctx: Context<AcceptDelivery>, rating: Option<u8>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(
+        order.status == OrderStatus::Delivered || order.status == OrderStatus::Disputed,
+        ErrorCode::InvalidOrderStatus
+    );
+    if let Some(rating) = rating {
+        require!((1..=MAX_RATING).contains(&rating), ErrorCode::InvalidRating);
+    }
+    // Accepting after a dispute means the dispute was unfounded
+    let disputed = order.status == OrderStatus::Disputed;
+    order.deposit_forfeited = disputed;
+
+    release_escrow(
+        order,
+        &ctx.accounts.order_vault_token_account,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.token_program,
+        ctx.program_id,
+    )?;
+    release_bond(order, &ctx.accounts.bond)?;
+
+    let now = ctx.accounts.clock.unix_timestamp;
+    order.accepted_at = now;
+    order.rating = rating.unwrap_or_default();
+
+    let reputation = &mut ctx.accounts.reputation;
+    let client_stats = &mut ctx.accounts.client_stats;
+    let score = &ctx.accounts.config.score;
+    record_completion(order, reputation, client_stats, score, now);
+    let weight = MintConfig::feedback_weight(
+        ctx.accounts.mint_config.as_deref(),
+        order,
+        ctx.accounts.mint.decimals,
+    );
+    // Feedback on an order the provider placed with itself counts for nobody
+    if !order.is_self_dealt() {
+        if disputed {
+            client_stats.disputes_lost += 1;
+        }
+        if rating.is_some_and(|rating| rating <= UNSATISFIED_RATING) {
+            client_stats.unsatisfied += 1;
+        }
+        reputation.orders_accepted += 1;
+        if let Some(rating) = rating {
+            reputation.ratings_count += 1;
+            reputation.ratings_sum += rating as u64;
+            reputation.weighted_ratings_count = reputation.weighted_ratings_count.saturating_add(weight);
+            reputation.weighted_ratings_sum = reputation
+                .weighted_ratings_sum
+                .saturating_add(weight.saturating_mul(rating as u64));
+            reputation.record(ScoreEvent::Rated { rating, weight }, score, now);
+        }
+    }
+
+    emit!(DeliveryAccepted {
+        order: order.key(),
+        amount: order.payout(),
+        rating,
+        weight,
+    });
+
+    Ok(())
+}
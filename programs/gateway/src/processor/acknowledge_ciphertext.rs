@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::context::AcknowledgeCiphertext;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::CiphertextAcknowledged;
+
+pub fn process_acknowledge_ciphertext(ctx: Context<AcknowledgeCiphertext>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Encrypted, ErrorCode::InvalidOrderStatus);
+
+    order.status = OrderStatus::Acknowledged;
+
+    emit!(CiphertextAcknowledged {
+        order: order.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use borsh::BorshDeserialize;
+use crate::context::AmendOrder;
+use crate::errors::ErrorCode;
+use crate::utils::load_ed25519_message;
+use crate::state::OrderStatus;
+use crate::events::OrderAmended;
+use crate::processor::deposit_bond::relock_bond;
+
+#[derive(Debug, BorshDeserialize)]
+pub struct Amendment {
+    pub order: Pubkey,
+    // Index of this amendment, keeps a signed amendment from being applied twice
+    pub amendment: u32,
+    pub price: u64,
+    pub deadline: Option<i64>,
+    pub price_valid_until: u64,
+}
+
+pub fn process_amend_order(ctx: Context<AmendOrder>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
+
+    let message = load_ed25519_message(&ctx.accounts.instructions, 0, &order.signer)?;
+    let amendment = Amendment::try_from_slice(&message)?;
+
+    require_keys_eq!(amendment.order, order.key(), ErrorCode::InvalidAmendment);
+    require!(amendment.amendment == order.amendments, ErrorCode::InvalidAmendment);
+    require!(amendment.price >= order.price, ErrorCode::InvalidAmendment);
+    require!(
+        amendment.price_valid_until > ctx.accounts.clock.unix_timestamp as u64,
+        ErrorCode::OfferExpired
+    );
+    // Moving the end of a vesting schedule would shrink what has already vested
+    require!(!(order.vesting && amendment.deadline.is_some()), ErrorCode::InvalidAmendment);
+
+    let old_price = order.price;
+    let old_deadline = order.deadline;
+    let difference = amendment.price - old_price;
+
+    // Client deposits the difference into the existing escrow
+    if difference > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    to: ctx.accounts.order_vault_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                }
+            ),
+            difference
+        )?;
+    }
+
+    order.price = amendment.price;
+    if let Some(deadline) = amendment.deadline {
+        order.deadline = deadline;
+    }
+    // The bond backs the amended price, not the original one
+    relock_bond(order, &ctx.accounts.bond, ctx.accounts.config.bond_leverage)?;
+    order.amendments += 1;
+
+    emit!(OrderAmended {
+        order: order.key(),
+        amendment: amendment.amendment,
+        old_price,
+        new_price: order.price,
+        old_deadline,
+        new_deadline: order.deadline,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Approve, Revoke, Transfer};
+use crate::context::CancelSubscription;
+use crate::state::{SubscriptionFunding, SubscriptionStatus};
+use crate::errors::ErrorCode;
+use crate::events::SubscriptionCancelled;
+
+// Periods that already started stay chargeable, every later one is refunded
+pub fn process_cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    require!(subscription.status == SubscriptionStatus::Active, ErrorCode::SubscriptionNotActive);
+
+    let elapsed = ctx.accounts.clock.unix_timestamp - subscription.started_at;
+    let started_periods = (elapsed / subscription.period + 1).min(subscription.periods as i64) as u32;
+    let periods = started_periods.max(subscription.periods_charged);
+    let refunded = subscription.price_per_period * (subscription.periods - periods) as u64;
+
+    subscription.periods = periods;
+    subscription.status = SubscriptionStatus::Cancelled;
+
+    match subscription.funding {
+        SubscriptionFunding::Prefunded => {
+            let subscription_id = subscription.subscription_id.to_le_bytes();
+            let (_, subscription_bump) = Pubkey::find_program_address(
+                &[b"subscription", subscription.client.as_ref(), subscription.provider.as_ref(), subscription_id.as_ref()],
+                ctx.program_id
+            );
+            let subscription_seeds = &[
+                b"subscription",
+                subscription.client.as_ref(),
+                subscription.provider.as_ref(),
+                subscription_id.as_ref(),
+                &[subscription_bump],
+            ];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.subscription_vault_token_account.to_account_info(),
+                        to: ctx.accounts.client_token_account.to_account_info(),
+                        authority: subscription.to_account_info(),
+                    },
+                    &[subscription_seeds]
+                ),
+                refunded,
+            )?;
+        }
+        // Nothing is escrowed, shrink the allowance to the periods still owed. If
+        // the client delegated the account to something else since, leave that be.
+        SubscriptionFunding::Delegated => {
+            let client_token_account = &ctx.accounts.client_token_account;
+            let owed = subscription.price_per_period * (periods - subscription.periods_charged) as u64;
+            if client_token_account.delegate == COption::Some(subscription.key()) {
+                if owed == 0 {
+                    token::revoke(CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Revoke {
+                            source: client_token_account.to_account_info(),
+                            authority: ctx.accounts.client.to_account_info(),
+                        }
+                    ))?;
+                } else {
+                    token::approve(
+                        CpiContext::new(
+                            ctx.accounts.token_program.to_account_info(),
+                            Approve {
+                                to: client_token_account.to_account_info(),
+                                delegate: subscription.to_account_info(),
+                                authority: ctx.accounts.client.to_account_info(),
+                            }
+                        ),
+                        owed
+                    )?;
+                }
+            }
+        }
+    }
+
+    emit!(SubscriptionCancelled {
+        subscription: subscription.key(),
+        periods,
+        refunded,
+    });
+
+    Ok(())
+}
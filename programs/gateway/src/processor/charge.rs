@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Transfer};
+use crate::context::Charge;
+use crate::state::SubscriptionFunding;
+use crate::errors::ErrorCode;
+use crate::events::SubscriptionCharged;
+
+// Records the deliverable for the next unpaid period and pays the provider
+// for it once that period is over.
+pub fn process_charge(ctx: Context<Charge>, result_hash: [u8; 32]) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    require!(subscription.periods_charged < subscription.periods, ErrorCode::SubscriptionFinished);
+
+    let period_index = subscription.periods_charged;
+    let period_ends_at = subscription.started_at + subscription.period * (period_index as i64 + 1);
+    require!(ctx.accounts.clock.unix_timestamp >= period_ends_at, ErrorCode::PeriodNotOver);
+
+    subscription.periods_charged += 1;
+    subscription.last_result_hash = result_hash;
+
+    let subscription_id = subscription.subscription_id.to_le_bytes();
+    let (_, subscription_bump) = Pubkey::find_program_address(
+        &[b"subscription", subscription.client.as_ref(), subscription.provider.as_ref(), subscription_id.as_ref()],
+        ctx.program_id
+    );
+    let subscription_seeds = &[
+        b"subscription",
+        subscription.client.as_ref(),
+        subscription.provider.as_ref(),
+        subscription_id.as_ref(),
+        &[subscription_bump],
+    ];
+    // The subscription PDA owns the vault, and is the delegate on the client's account
+    let from = match subscription.funding {
+        SubscriptionFunding::Prefunded => ctx.accounts.subscription_vault_token_account.to_account_info(),
+        SubscriptionFunding::Delegated => {
+            require!(
+                ctx.accounts.client_token_account.delegate == COption::Some(subscription.key()),
+                ErrorCode::TokenAccountDelegated
+            );
+            ctx.accounts.client_token_account.to_account_info()
+        }
+    };
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from,
+                to: ctx.accounts.vault_token_account.to_account_info(),
+                authority: subscription.to_account_info(),
+            },
+            &[subscription_seeds]
+        ),
+        subscription.price_per_period,
+    )?;
+
+    emit!(SubscriptionCharged {
+        subscription: subscription.key(),
+        period_index,
+        result_hash,
+        amount: subscription.price_per_period,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::ClaimVested;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::VestedClaimed;
+
+pub fn process_claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.vesting, ErrorCode::NotVestingOrder);
+    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
+
+    let (order_account, order_bump) = Pubkey::find_program_address(
+        &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
+        ctx.program_id
+    );
+    require!(order_account == order.key(), ErrorCode::InvalidOrderAccount);
+    require_keys_eq!(
+        ctx.accounts.order_vault_token_account.owner,
+        order.key(),
+        ErrorCode::InvalidOrderVaultTokenAccountOwner
+    );
+
+    let amount = order.vested(ctx.accounts.clock.unix_timestamp).saturating_sub(order.vested_claimed);
+    require!(amount > 0, ErrorCode::InsufficientFunds);
+    order.vested_claimed += amount;
+
+    let vault_authority_seeds = &[
+        b"order",
+        order.user.as_ref(),
+        order.job_hash.as_ref(),
+        &[order_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.order_vault_token_account.to_account_info(),
+                to: ctx.accounts.vault_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+        amount,
+    )?;
+
+    emit!(VestedClaimed {
+        order: order.key(),
+        amount,
+        vested_claimed: order.vested_claimed,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Transfer};
+use borsh::BorshDeserialize;
+use crate::context::CloseChannel;
+use crate::state::{ChannelStatus, ScoreEvent};
+use crate::errors::ErrorCode;
+use crate::utils::load_ed25519_message;
+use crate::events::ChannelClosed;
+
+/// Voucher signed off-chain by the client, each one for a higher amount
+/// than the last. The provider settles with the latest.
+#[derive(Debug, BorshDeserialize)]
+pub struct Voucher {
+    pub channel: Pubkey,
+    pub amount: u64,
+}
+
+pub fn process_close_channel(ctx: Context<CloseChannel>) -> Result<()> {
+    let channel = &mut ctx.accounts.channel;
+    require!(channel.status == ChannelStatus::Open, ErrorCode::ChannelClosed);
+
+    let message = load_ed25519_message(&ctx.accounts.instructions, 0, &channel.client)?;
+    let voucher = Voucher::try_from_slice(&message)?;
+    require_keys_eq!(voucher.channel, channel.key(), ErrorCode::InvalidInstructionData);
+    require!(voucher.amount <= channel.deposit, ErrorCode::InsufficientFunds);
+
+    let now = ctx.accounts.clock.unix_timestamp;
+    let refunded = channel.deposit - voucher.amount;
+    channel.settled = voucher.amount;
+    channel.status = ChannelStatus::Closed;
+
+    let channel_id = channel.channel_id.to_le_bytes();
+    let (_, channel_bump) = Pubkey::find_program_address(
+        &[b"channel", channel.client.as_ref(), channel.provider.as_ref(), channel_id.as_ref()],
+        ctx.program_id
+    );
+    let channel_seeds = &[
+        b"channel",
+        channel.client.as_ref(),
+        channel.provider.as_ref(),
+        channel_id.as_ref(),
+        &[channel_bump],
+    ];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.channel_vault_token_account.to_account_info(),
+                to: ctx.accounts.vault_token_account.to_account_info(),
+                authority: channel.to_account_info(),
+            },
+            &[channel_seeds]
+        ),
+        voucher.amount,
+    )?;
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.channel_vault_token_account.to_account_info(),
+                to: ctx.accounts.client_token_account.to_account_info(),
+                authority: channel.to_account_info(),
+            },
+            &[channel_seeds]
+        ),
+        refunded,
+    )?;
+
+    token::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.channel_vault_token_account.to_account_info(),
+                destination: ctx.accounts.client.to_account_info(),
+                authority: channel.to_account_info(),
+            },
+            &[channel_seeds]
+        ),
+    )?;
+
+    // A settled channel counts towards reputation like a completed order,
+    // unless nothing was paid or the provider opened it with itself
+    let duration = now - channel.opened_at;
+    if voucher.amount > 0 && channel.client != channel.provider {
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.provider = channel.provider;
+        reputation.orders_completed += 1;
+        reputation.volume = reputation.volume.saturating_add(voucher.amount);
+        reputation.total_duration = reputation.total_duration.saturating_add(duration.max(0) as u64);
+        reputation.record(
+            ScoreEvent::Completed { late: false, volume: voucher.amount },
+            &ctx.accounts.config.score,
+            now,
+        );
+    }
+
+    emit!(ChannelClosed {
+        channel: channel.key(),
+        amount: voucher.amount,
+        refunded,
+        duration,
+    });
+
+    Ok(())
+}
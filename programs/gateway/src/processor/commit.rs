@@ -1,46 +1,55 @@
-use anchor_lang::{prelude::*, solana_program};
-use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
 use borsh::BorshDeserialize;
 use crate::context::Commit;
 use crate::errors::ErrorCode;
-use crate::utils::check_ed25519_data;
-use crate::state::OrderStatus;
+use crate::utils::load_ed25519_message;
+use crate::state::{ClientStats, Config, OrderStatus};
 use crate::events::Start;
+use crate::processor::deposit_bond::lock_bond;
+
+/// Layout version of the signed offer, bumped whenever its fields change so a
+/// stale signature can never be read against a newer layout
+pub const OFFER_VERSION: u8 = 1;
 
 #[derive(Debug, BorshDeserialize)]
 pub struct Order {
+    pub version: u8,
     pub user: [u8; 32],
     pub job_hash: [u8; 32],
     pub price: u64,
     pub mint: Pubkey,
     pub price_valid_until: u64,
     pub deadline: i64,
+    pub vesting: bool,
+    pub grace_period: i64,
+    pub penalty_bps_per_hour: u16,
+    pub bonus: u64,
+    pub bonus_deadline: i64,
+    // Paid by the client to whoever relays a gasless commit, see `commit_relayed`
+    pub relayer_fee: u64,
+    pub client_deposit: u64,
+    // Refuse clients rating more than this share of their orders unsatisfied, 0 for no limit
+    pub max_client_unsatisfied_bps: u16,
 }
 
 pub fn process_commit(ctx: Context<Commit>, job_hash: [u8; 32]) -> Result<()> {
-    let ix = load_instruction_at_checked(0, &ctx.accounts.instructions)?;
-    require_keys_eq!(ix.program_id, solana_program::ed25519_program::id(), ErrorCode::InvalidProgramId);
-
     let config = &ctx.accounts.config;
-    let (key, message) =  check_ed25519_data(&ix.data);
-
+    let message = load_ed25519_message(&ctx.accounts.instructions, 0, &config.authority_signer)?;
 
     let order_decoded = Order::try_from_slice(&message)?;
+    require!(order_decoded.version == OFFER_VERSION, ErrorCode::UnsupportedOfferVersion);
 
-    require!(key == config.authority_signer.to_bytes(), ErrorCode::InvalidSignature);
     require!(order_decoded.job_hash == job_hash, ErrorCode::InvalidJobHash);
     require!(order_decoded.price_valid_until > Clock::get()?.unix_timestamp as u64, ErrorCode::OfferExpired);
     require_keys_eq!(order_decoded.mint, ctx.accounts.mint.key(), ErrorCode::InvalidMint);
 
     let order = &mut ctx.accounts.order;
-    order.user = ctx.accounts.user.key();
-    order.job_hash = job_hash;
-    order.status = OrderStatus::Started;
-    order.started_at = ctx.accounts.clock.unix_timestamp;
-    order.price = order_decoded.price;
-    order.price_valid_until = order_decoded.price_valid_until;
-    order.deadline = order_decoded.deadline;
+    order.user = ctx.accounts.beneficiary.key();
+    order.payer = ctx.accounts.user.key();
+    apply_offer(order, &order_decoded, config, ctx.accounts.clock.unix_timestamp);
+    check_client(&order_decoded, &mut ctx.accounts.client_stats, order)?;
+    lock_bond(order, &ctx.accounts.bond, config.bond_leverage, config.bond_slash_bps)?;
 
     // Transfer tokens to vault
     token::transfer(
@@ -52,7 +61,7 @@ pub fn process_commit(ctx: Context<Commit>, job_hash: [u8; 32]) -> Result<()> {
                 authority: ctx.accounts.user.to_account_info(),
             }
         ),
-        order_decoded.price
+        order.escrowed()
     )?;
     
     emit!(Start {
@@ -60,4 +69,41 @@ pub fn process_commit(ctx: Context<Commit>, job_hash: [u8; 32]) -> Result<()> {
     });
     
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Starts `order` on the terms of a signed offer
+pub fn apply_offer(order: &mut crate::state::Order, offer: &Order, config: &Config, now: i64) {
+    order.job_hash = offer.job_hash;
+    order.status = OrderStatus::Started;
+    order.started_at = now;
+    order.price = offer.price;
+    order.price_valid_until = offer.price_valid_until;
+    order.deadline = offer.deadline;
+    order.mint = offer.mint;
+    order.provider = config.authority;
+    order.signer = config.authority_signer;
+    order.vesting = offer.vesting;
+    order.grace_period = offer.grace_period;
+    order.penalty_bps_per_hour = offer.penalty_bps_per_hour;
+    order.bonus = offer.bonus;
+    order.bonus_deadline = offer.bonus_deadline;
+    order.client_deposit = offer.client_deposit;
+}
+
+/// Enforces the offer's client and its requirement, and counts the new order.
+/// The offer names the client it is made to, so a signed offer cannot be
+/// redeemed for another beneficiary.
+pub fn check_client(offer: &Order, client_stats: &mut ClientStats, order: &crate::state::Order) -> Result<()> {
+    require!(offer.user == order.user.to_bytes(), ErrorCode::InvalidUser);
+    if offer.max_client_unsatisfied_bps > 0 {
+        require!(
+            client_stats.unsatisfied_bps() <= offer.max_client_unsatisfied_bps as u64,
+            ErrorCode::ClientReputationTooLow
+        );
+    }
+    if !order.is_self_dealt() {
+        client_stats.client = order.user;
+        client_stats.orders_placed += 1;
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::CommitEvaluation;
+use crate::errors::ErrorCode;
+use crate::state::OrderStatus;
+use crate::events::Start;
+use crate::processor::deposit_bond::lock_bond;
+
+// Accepts a price the service posted with `evaluate`. The evaluation was
+// written by the config authority itself, so no Ed25519 offer is needed.
+pub fn process_commit_evaluation(ctx: Context<CommitEvaluation>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Evaluated, ErrorCode::InvalidOrderStatus);
+    require!(order.price_valid_until > ctx.accounts.clock.unix_timestamp as u64, ErrorCode::OfferExpired);
+
+    order.status = OrderStatus::Started;
+    order.started_at = ctx.accounts.clock.unix_timestamp;
+
+    if !order.is_self_dealt() {
+        let client_stats = &mut ctx.accounts.client_stats;
+        client_stats.client = order.user;
+        client_stats.orders_placed += 1;
+    }
+    let config = &ctx.accounts.config;
+    lock_bond(order, &ctx.accounts.bond, config.bond_leverage, config.bond_slash_bps)?;
+
+    // Transfer tokens to vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.order_vault_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            }
+        ),
+        order.price
+    )?;
+
+    emit!(Start {
+        order: order.key(),
+    });
+
+    Ok(())
+}
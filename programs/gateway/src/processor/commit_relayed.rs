@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Transfer};
+use borsh::BorshDeserialize;
+use crate::context::CommitRelayed;
+use crate::errors::ErrorCode;
+use crate::utils::load_ed25519_message;
+use crate::events::{OrderRelayed, Start};
+use crate::processor::commit::{apply_offer, check_client, Order, OFFER_VERSION};
+use crate::processor::deposit_bond::lock_bond;
+
+#[derive(Debug, BorshDeserialize)]
+pub struct RelayAuthorization {
+    // sha256 of the offer message the client agrees to pay for
+    pub offer_hash: [u8; 32],
+}
+
+// Gasless commit. Instruction 0 verifies the provider's offer, instruction 1
+// the client's authorization of that exact offer. Tokens are pulled through
+// the client's delegate approval of `relay_authority`, which must not be
+// given on an account a delegated subscription draws from, see `SubscriptionFunding`.
+pub fn process_commit_relayed(ctx: Context<CommitRelayed>, job_hash: [u8; 32]) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let offer_message = load_ed25519_message(&ctx.accounts.instructions, 0, &config.authority_signer)?;
+    let authorization_message = load_ed25519_message(&ctx.accounts.instructions, 1, &ctx.accounts.user.key())?;
+
+    let offer = Order::try_from_slice(&offer_message)?;
+    require!(offer.version == OFFER_VERSION, ErrorCode::UnsupportedOfferVersion);
+    let authorization = RelayAuthorization::try_from_slice(&authorization_message)?;
+    require!(authorization.offer_hash == hash(&offer_message).to_bytes(), ErrorCode::InvalidAuthorization);
+
+    require!(offer.job_hash == job_hash, ErrorCode::InvalidJobHash);
+    require!(offer.price_valid_until > ctx.accounts.clock.unix_timestamp as u64, ErrorCode::OfferExpired);
+    require_keys_eq!(offer.mint, ctx.accounts.mint.key(), ErrorCode::InvalidMint);
+
+    let order = &mut ctx.accounts.order;
+    order.user = ctx.accounts.user.key();
+    order.payer = ctx.accounts.user.key();
+    apply_offer(order, &offer, config, ctx.accounts.clock.unix_timestamp);
+    check_client(&offer, &mut ctx.accounts.client_stats, order)?;
+    lock_bond(order, &ctx.accounts.bond, config.bond_leverage, config.bond_slash_bps)?;
+
+    require!(
+        ctx.accounts.user_token_account.delegate == COption::Some(ctx.accounts.relay_authority.key()),
+        ErrorCode::TokenAccountDelegated
+    );
+    let (_, relay_authority_bump) = Pubkey::find_program_address(&[b"relay-authority"], ctx.program_id);
+    let relay_authority_seeds: &[&[u8]] = &[b"relay-authority", &[relay_authority_bump]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.order_vault_token_account.to_account_info(),
+                authority: ctx.accounts.relay_authority.to_account_info(),
+            },
+            &[relay_authority_seeds]
+        ),
+        order.escrowed()
+    )?;
+
+    if offer.relayer_fee > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    to: ctx.accounts.relayer_token_account.to_account_info(),
+                    authority: ctx.accounts.relay_authority.to_account_info(),
+                },
+                &[relay_authority_seeds]
+            ),
+            offer.relayer_fee
+        )?;
+    }
+
+    emit!(OrderRelayed {
+        order: order.key(),
+        relayer: ctx.accounts.relayer.key(),
+        relayer_fee: offer.relayer_fee,
+    });
+
+    emit!(Start {
+        order: order.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Approve, Transfer};
+use crate::context::CreateSubscription;
+use crate::state::{SubscriptionFunding, SubscriptionStatus};
+use crate::errors::ErrorCode;
+use crate::events::SubscriptionCreated;
+
+pub fn process_create_subscription(
+    ctx: Context<CreateSubscription>,
+    subscription_id: u64,
+    period: i64,
+    price_per_period: u64,
+    periods: u32,
+    funding: SubscriptionFunding,
+) -> Result<()> {
+    require!(period > 0 && periods > 0, ErrorCode::InvalidDeadline);
+    let total = price_per_period
+        .checked_mul(periods as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.client = ctx.accounts.client.key();
+    subscription.provider = ctx.accounts.provider.key();
+    subscription.mint = ctx.accounts.mint.key();
+    subscription.subscription_id = subscription_id;
+    subscription.period = period;
+    subscription.price_per_period = price_per_period;
+    subscription.periods = periods;
+    subscription.periods_charged = 0;
+    subscription.started_at = ctx.accounts.clock.unix_timestamp;
+    subscription.funding = funding;
+    subscription.status = SubscriptionStatus::Active;
+
+    match funding {
+        SubscriptionFunding::Prefunded => token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.client_token_account.to_account_info(),
+                    to: ctx.accounts.subscription_vault_token_account.to_account_info(),
+                    authority: ctx.accounts.client.to_account_info(),
+                }
+            ),
+            total
+        )?,
+        SubscriptionFunding::Delegated => {
+            // Approving would silently replace a delegate another flow relies on
+            require!(
+                ctx.accounts.client_token_account.delegate.is_none(),
+                ErrorCode::TokenAccountDelegated
+            );
+            token::approve(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Approve {
+                        to: ctx.accounts.client_token_account.to_account_info(),
+                        delegate: subscription.to_account_info(),
+                        authority: ctx.accounts.client.to_account_info(),
+                    }
+                ),
+                total
+            )?
+        }
+    }
+
+    emit!(SubscriptionCreated {
+        subscription: subscription.key(),
+        client: subscription.client,
+        provider: subscription.provider,
+        period,
+        price_per_period,
+        periods,
+    });
+
+    Ok(())
+}
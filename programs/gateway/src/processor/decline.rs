@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, CloseAccount, Transfer};
 use crate::context::Decline;
-use crate::state::{OrderStatus};
+use crate::state::{OrderStatus, ScoreEvent};
 use crate::errors::ErrorCode;
 use crate::events::Abort;
+use crate::processor::deposit_bond::{slash_bond, unlock_bond};
 
 pub fn process_decline(ctx: Context<Decline>) -> Result<()> {
     let order = &mut ctx.accounts.order;
-    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
+    require!(
+        matches!(order.status, OrderStatus::Started | OrderStatus::Disputed | OrderStatus::Revision),
+        ErrorCode::InvalidOrderStatus
+    );
 
     let (order_account, order_bump) = Pubkey::find_program_address(
         &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
@@ -38,7 +42,7 @@ pub fn process_decline(ctx: Context<Decline>) -> Result<()> {
             },
             &[vault_authority_seeds]
         ),
-        order.price,
+        order.escrowed(),
     )?;
 
     // remove vault token account
@@ -55,9 +59,38 @@ pub fn process_decline(ctx: Context<Decline>) -> Result<()> {
         ),
     )?;
 
+    // Conceding a dispute counts as losing it
+    unlock_bond(order, ctx.accounts.bond.as_mut())?;
+    if order.bonded && order.status == OrderStatus::Disputed {
+        let bond = ctx.accounts.bond.as_mut().ok_or(ErrorCode::BondRequired)?;
+        slash_bond(
+            order,
+            bond,
+            ctx.accounts.bond_vault_token_account.as_ref(),
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            ctx.program_id,
+        )?;
+        ctx.accounts.reputation.bond = bond.amount;
+    }
+
+    let self_dealt = order.is_self_dealt();
+    if !self_dealt && order.status == OrderStatus::Disputed {
+        let client_stats = &mut ctx.accounts.client_stats;
+        client_stats.client = order.user;
+        client_stats.disputes_won += 1;
+    }
+
     order.status = OrderStatus::Aborted;
     order.completed_at = ctx.accounts.clock.unix_timestamp;
 
+    if !self_dealt {
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.provider = order.provider;
+        reputation.orders_aborted += 1;
+        reputation.record(ScoreEvent::Aborted, &ctx.accounts.config.score, order.completed_at);
+    }
+
     emit!(Abort {
         order: order.key(),
     });
@@ -1,20 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, CloseAccount, Transfer};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::context::Deliver;
-use crate::state::{OrderStatus};
+use crate::state::{Order, OrderStatus};
 use crate::errors::ErrorCode;
-use crate::events::Completed;
+use crate::events::{BonusSettled, Completed, LateDelivery};
 
 pub fn process_deliver(ctx: Context<Deliver>, result_hash: [u8; 32]) -> Result<()> {
     let order = &mut ctx.accounts.order;
-    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
-    require!(ctx.accounts.clock.unix_timestamp < order.deadline, ErrorCode::DeliverAfterDeadline);
-    
-    order.result_hash = result_hash;
-    order.status = OrderStatus::Completed;
-    order.completed_at = ctx.accounts.clock.unix_timestamp;
+    let now = ctx.accounts.clock.unix_timestamp;
+    require!(
+        order.status == OrderStatus::Started || order.status == OrderStatus::Revision,
+        ErrorCode::InvalidOrderStatus
+    );
+    let revision = order.status == OrderStatus::Revision;
+    let deliver_before = if revision { order.revision_deadline } else { order.deadline + order.grace_period };
+    require!(now < deliver_before, ErrorCode::DeliverAfterDeadline);
 
-    let (order_account, order_bump) = Pubkey::find_program_address(
+    let (order_account, _) = Pubkey::find_program_address(
         &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
         ctx.program_id
     );
@@ -25,43 +27,94 @@ pub fn process_deliver(ctx: Context<Deliver>, result_hash: [u8; 32]) -> Result<(
         ErrorCode::InvalidOrderVaultTokenAccountOwner
     );
 
+    // Penalty and bonus are judged on the first delivery only
+    if !revision {
+        settle_delivery_terms(
+            order,
+            &ctx.accounts.order_vault_token_account,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            ctx.program_id,
+            now,
+        )?;
+    }
+
+    // Funds stay in the order vault until the challenge window is over, see `finalize`
+    order.result_hash = result_hash;
+    order.status = OrderStatus::Delivered;
+    // A redelivery restarts the challenge window, but timeliness stays with the first delivery
+    if !revision {
+        order.completed_at = now;
+    }
+    order.challenge_ends_at = now + ctx.accounts.config.challenge_window;
+
+    emit!(Completed {
+        order: order.key(),
+        result_hash,
+    });
+    
+    Ok(())
+}
+
+/// Applies the offer's delivery-time terms: a late delivery is paid less and
+/// an early one earns the bonus. Whatever the provider does not get goes back
+/// to the client straight away.
+pub fn settle_delivery_terms<'info>(
+    order: &mut Account<'info, Order>,
+    order_vault_token_account: &Account<'info, TokenAccount>,
+    user_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    let penalty = order.penalty_at(now);
+    order.late_penalty = penalty;
+    if now >= order.deadline {
+        emit!(LateDelivery {
+            order: order.key(),
+            late_by: now - order.deadline,
+            penalty,
+        });
+    }
+
+    let mut returned = penalty;
+    if order.bonus > 0 {
+        order.bonus_earned = now < order.bonus_deadline;
+        order.bonus_forfeited = !order.bonus_earned;
+        if order.bonus_forfeited {
+            returned += order.bonus;
+        }
+        emit!(BonusSettled {
+            order: order.key(),
+            bonus: order.bonus,
+            earned: order.bonus_earned,
+        });
+    }
+
+    if returned == 0 {
+        return Ok(());
+    }
 
+    let (_, order_bump) = Pubkey::find_program_address(
+        &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
+        program_id
+    );
     let vault_authority_seeds = &[
         b"order",
         order.user.as_ref(),
-        order.job_hash.as_ref(), // assuming this is how `order` PDA was created
+        order.job_hash.as_ref(),
         &[order_bump],
     ];
-    // transfer all tokens from order_vault_token_account to vault_token_account
     token::transfer(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {  
-                from: ctx.accounts.order_vault_token_account.to_account_info(),
-                to: ctx.accounts.vault_token_account.to_account_info(),
-                authority: order.to_account_info(),
-            },
-            &[vault_authority_seeds]
-        ),
-        order.price,
-    )?;
-
-    // 2️⃣ Close order_vault token account and send rent lamports to the recipient
-    token::close_account(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: ctx.accounts.order_vault_token_account.to_account_info(),
-                destination: ctx.accounts.authority.to_account_info(),
+            token_program.to_account_info(),
+            Transfer {
+                from: order_vault_token_account.to_account_info(),
+                to: user_token_account.to_account_info(),
                 authority: order.to_account_info(),
             },
             &[vault_authority_seeds]
         ),
-    )?;
-    emit!(Completed {
-        order: order.key(),
-        result_hash,
-    });
-    
-    Ok(())
-} 
\ No newline at end of file
+        returned,
+    )
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::context::DeliverEncrypted;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::CiphertextCommitted;
+
+// Fair-exchange delivery. The provider commits to an encrypted result and the
+// hash of its key, the client confirms it received the ciphertext, and
+// revealing the matching key on-chain is what releases payment.
+
+pub fn process_deliver_encrypted(
+    ctx: Context<DeliverEncrypted>,
+    ciphertext_hash: [u8; 32],
+    key_hash: [u8; 32],
+) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
+    require!(
+        ctx.accounts.clock.unix_timestamp < order.deadline + order.grace_period,
+        ErrorCode::DeliverAfterDeadline
+    );
+
+    order.result_hash = ciphertext_hash;
+    order.key_hash = key_hash;
+    order.status = OrderStatus::Encrypted;
+
+    emit!(CiphertextCommitted {
+        order: order.key(),
+        ciphertext_hash,
+        key_hash,
+    });
+
+    Ok(())
+}
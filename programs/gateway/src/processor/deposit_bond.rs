@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::context::DepositBond;
+use crate::state::{Bond, Order, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+use crate::events::{BondDeposited, BondSlashed};
+
+pub fn process_deposit_bond(ctx: Context<DepositBond>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InsufficientFunds);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                to: ctx.accounts.bond_vault_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            }
+        ),
+        amount
+    )?;
+
+    let bond = &mut ctx.accounts.bond;
+    bond.provider = ctx.accounts.authority.key();
+    bond.mint = ctx.accounts.mint.key();
+    bond.amount = bond.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    let reputation = &mut ctx.accounts.reputation;
+    reputation.provider = bond.provider;
+    reputation.bond = bond.amount;
+
+    emit!(BondDeposited {
+        provider: bond.provider,
+        mint: bond.mint,
+        amount,
+        total: bond.amount,
+    });
+
+    Ok(())
+}
+
+// Bond accounting shared by the order lifecycle. Every order placed with a
+// provider holding a bond is bonded and holds `bond_locked` of its capacity
+// until the order is final: accepted, finalized, refunded or aborted.
+
+/// Backs a new order with the provider's bond. Commit paths always pass the
+/// bond PDA, so a provider with a bond cannot have it left out; an empty
+/// account means the provider never deposited one.
+pub fn lock_bond(order: &mut Order, bond_info: &AccountInfo, leverage: u16, slash_bps: u16) -> Result<()> {
+    let Some(mut bond) = load_bond(bond_info)? else {
+        return Ok(());
+    };
+    // A fully withdrawn bond backs nothing
+    if bond.amount == 0 && bond.outstanding == 0 {
+        return Ok(());
+    }
+    let outstanding = bond.outstanding.checked_add(order.price).ok_or(ErrorCode::MathOverflow)?;
+    require!(outstanding <= bond.capacity(leverage), ErrorCode::BondCapacityExceeded);
+    bond.outstanding = outstanding;
+    order.bonded = true;
+    order.bond_slash_bps = slash_bps;
+    order.bond_locked = order.price;
+    bond.try_serialize(&mut &mut bond_info.try_borrow_mut_data()?[..])
+}
+
+/// Grows the capacity held by a bonded order to its amended price
+pub fn relock_bond(order: &mut Order, bond_info: &AccountInfo, leverage: u16) -> Result<()> {
+    if !order.bonded || order.bond_locked == order.price {
+        return Ok(());
+    }
+    let mut bond = load_bond(bond_info)?.ok_or(ErrorCode::BondRequired)?;
+    let outstanding = bond
+        .outstanding
+        .saturating_sub(order.bond_locked)
+        .checked_add(order.price)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(outstanding <= bond.capacity(leverage), ErrorCode::BondCapacityExceeded);
+    bond.outstanding = outstanding;
+    order.bond_locked = order.price;
+    bond.try_serialize(&mut &mut bond_info.try_borrow_mut_data()?[..])
+}
+
+/// Frees the capacity a finished order held, for instructions that take the
+/// bond PDA the same way the commit paths do.
+pub fn release_bond(order: &mut Order, bond_info: &AccountInfo) -> Result<()> {
+    if order.bond_locked == 0 {
+        return Ok(());
+    }
+    let mut bond = load_bond(bond_info)?.ok_or(ErrorCode::BondRequired)?;
+    bond.outstanding = bond.outstanding.saturating_sub(order.bond_locked);
+    order.bond_locked = 0;
+    bond.try_serialize(&mut &mut bond_info.try_borrow_mut_data()?[..])
+}
+
+/// Frees the capacity held by an order that ends without being paid
+pub fn unlock_bond(order: &mut Order, bond: Option<&mut Account<Bond>>) -> Result<()> {
+    if order.bond_locked == 0 {
+        return Ok(());
+    }
+    let bond = bond.ok_or(ErrorCode::BondRequired)?;
+    bond.outstanding = bond.outstanding.saturating_sub(order.bond_locked);
+    order.bond_locked = 0;
+    Ok(())
+}
+
+fn load_bond(bond_info: &AccountInfo) -> Result<Option<Bond>> {
+    if bond_info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*bond_info.owner, crate::ID, ErrorCode::InvalidProgramId);
+    Ok(Some(Bond::try_deserialize(&mut &bond_info.try_borrow_data()?[..])?))
+}
+
+/// Pays the order's share of the bond to `recipient`, returns the amount slashed
+pub fn slash_bond<'info>(
+    order: &Account<'info, Order>,
+    bond: &mut Account<'info, Bond>,
+    bond_vault_token_account: Option<&Account<'info, TokenAccount>>,
+    recipient: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    program_id: &Pubkey,
+) -> Result<u64> {
+    let amount = (bond.amount as u128 * order.bond_slash_bps as u128 / BPS_DENOMINATOR) as u64;
+    if amount == 0 {
+        return Ok(0);
+    }
+    let bond_vault_token_account = bond_vault_token_account.ok_or(ErrorCode::BondRequired)?;
+
+    let (_, bond_bump) = Pubkey::find_program_address(
+        &[b"bond", bond.provider.as_ref(), bond.mint.as_ref()],
+        program_id
+    );
+    let bond_seeds = &[
+        b"bond",
+        bond.provider.as_ref(),
+        bond.mint.as_ref(),
+        &[bond_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: bond_vault_token_account.to_account_info(),
+                to: recipient.to_account_info(),
+                authority: bond.to_account_info(),
+            },
+            &[bond_seeds]
+        ),
+        amount,
+    )?;
+    bond.amount -= amount;
+
+    emit!(BondSlashed {
+        order: order.key(),
+        provider: bond.provider,
+        amount,
+    });
+
+    Ok(amount)
+}
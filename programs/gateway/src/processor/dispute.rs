@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::context::Dispute;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::DisputeOpened;
+
+// Freezes a delivered order's escrow. There is no arbiter: the provider
+// resolves it with `decline` (client refunded) or the client with `accept_delivery`.
+// If neither happens before `dispute_ends_at` the client may `refund`.
+pub fn process_dispute(ctx: Context<Dispute>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Delivered, ErrorCode::InvalidOrderStatus);
+    require!(ctx.accounts.clock.unix_timestamp < order.challenge_ends_at, ErrorCode::ChallengeWindowClosed);
+
+    order.status = OrderStatus::Disputed;
+    order.dispute_ends_at = ctx.accounts.clock.unix_timestamp + ctx.accounts.config.dispute_period;
+
+    if !order.is_self_dealt() {
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.provider = order.provider;
+        reputation.orders_disputed += 1;
+
+        let client_stats = &mut ctx.accounts.client_stats;
+        client_stats.client = order.user;
+        client_stats.disputes_opened += 1;
+    }
+
+    emit!(DisputeOpened {
+        order: order.key(),
+        ends_at: order.dispute_ends_at,
+    });
+
+    Ok(())
+}
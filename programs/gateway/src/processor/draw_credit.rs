@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use borsh::BorshDeserialize;
+use crate::context::DrawCredit;
+use crate::errors::ErrorCode;
+use crate::utils::load_ed25519_message;
+use crate::events::CreditDrawn;
+
+/// Usage receipt signed off-chain by the client. Amounts are cumulative so
+/// the provider only ever needs to submit the latest one.
+#[derive(Debug, BorshDeserialize)]
+pub struct UsageReceipt {
+    pub credit: Pubkey,
+    pub cumulative_amount: u64,
+    pub counter: u64,
+}
+
+pub fn process_draw_credit(ctx: Context<DrawCredit>) -> Result<()> {
+    let credit = &mut ctx.accounts.credit;
+    let message = load_ed25519_message(&ctx.accounts.instructions, 0, &credit.client)?;
+    let receipt = UsageReceipt::try_from_slice(&message)?;
+
+    require_keys_eq!(receipt.credit, credit.key(), ErrorCode::InvalidInstructionData);
+    require!(receipt.counter > credit.counter, ErrorCode::StaleReceipt);
+    require!(receipt.cumulative_amount > credit.drawn, ErrorCode::StaleReceipt);
+    require!(receipt.cumulative_amount <= credit.deposited, ErrorCode::InsufficientFunds);
+
+    let amount = receipt.cumulative_amount - credit.drawn;
+    credit.drawn = receipt.cumulative_amount;
+    credit.counter = receipt.counter;
+
+    let (_, credit_bump) = Pubkey::find_program_address(
+        &[b"credit", credit.client.as_ref(), credit.provider.as_ref(), credit.mint.as_ref()],
+        ctx.program_id
+    );
+    let credit_seeds = &[
+        b"credit",
+        credit.client.as_ref(),
+        credit.provider.as_ref(),
+        credit.mint.as_ref(),
+        &[credit_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.credit_vault_token_account.to_account_info(),
+                to: ctx.accounts.vault_token_account.to_account_info(),
+                authority: credit.to_account_info(),
+            },
+            &[credit_seeds]
+        ),
+        amount,
+    )?;
+
+    emit!(CreditDrawn {
+        credit: credit.key(),
+        amount,
+        cumulative_amount: receipt.cumulative_amount,
+        counter: receipt.counter,
+    });
+
+    Ok(())
+}
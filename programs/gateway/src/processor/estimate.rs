@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::context::Estimate;
+use crate::state::OrderStatus;
+use crate::events::OrderPlaced;
+
+pub fn process_estimate(ctx: Context<Estimate>, job_hash: [u8; 32], budget: u64) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    order.user = ctx.accounts.user.key();
+    order.payer = ctx.accounts.user.key();
+    order.job_hash = job_hash;
+    order.mint = ctx.accounts.mint.key();
+    order.budget = budget;
+    order.status = OrderStatus::Requested;
+
+    emit!(OrderPlaced {
+        user: order.user,
+        job_hash,
+        order: order.key(),
+    });
+
+    Ok(())
+}
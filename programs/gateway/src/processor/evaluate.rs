@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::context::Evaluate;
+use crate::state::OrderStatus;
+use crate::errors::ErrorCode;
+use crate::events::EvaluationMade;
+
+pub fn process_evaluate(
+    ctx: Context<Evaluate>,
+    price: u64,
+    price_valid_until: u64,
+    deadline: i64,
+) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Requested, ErrorCode::InvalidOrderStatus);
+    require!(price <= order.budget, ErrorCode::BudgetExceeded);
+
+    order.price = price;
+    order.price_valid_until = price_valid_until;
+    order.deadline = deadline;
+    order.provider = ctx.accounts.config.authority;
+    order.signer = ctx.accounts.config.authority_signer;
+    order.status = OrderStatus::Evaluated;
+
+    emit!(EvaluationMade {
+        order: order.key(),
+        price,
+        price_valid_until,
+        deadline,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::context::Finalize;
+use crate::state::{ClientStats, Order, OrderStatus, Reputation, ScoreEvent, ScoreParams};
+use crate::errors::ErrorCode;
+use crate::events::{DepositSettled, Finalized};
+use crate::processor::deposit_bond::release_bond;
+
+pub fn process_finalize(ctx: Context<Finalize>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    let now = ctx.accounts.clock.unix_timestamp;
+    if order.vesting && order.status == OrderStatus::Started {
+        // A vesting order is done once its schedule has run out: the provider
+        // has earned the full price without delivering anything
+        require!(now >= order.deadline, ErrorCode::VestingInProgress);
+        order.completed_at = now;
+        return_unearned_bonus(
+            order,
+            &ctx.accounts.order_vault_token_account,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            ctx.program_id,
+        )?;
+    } else {
+        require!(order.status == OrderStatus::Delivered, ErrorCode::InvalidOrderStatus);
+        require!(now >= order.challenge_ends_at, ErrorCode::ChallengeWindowOpen);
+    }
+
+    release_escrow(
+        order,
+        &ctx.accounts.order_vault_token_account,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.token_program,
+        ctx.program_id,
+    )?;
+    release_bond(order, &ctx.accounts.bond)?;
+    record_completion(
+        order,
+        &mut ctx.accounts.reputation,
+        &mut ctx.accounts.client_stats,
+        &ctx.accounts.config.score,
+        now,
+    );
+
+    emit!(Finalized {
+        order: order.key(),
+        amount: order.payout(),
+    });
+
+    Ok(())
+}
+
+/// Sends an early-delivery bonus that was never earned back to the payer.
+fn return_unearned_bonus<'info>(
+    order: &mut Account<'info, Order>,
+    order_vault_token_account: &Account<'info, TokenAccount>,
+    payer_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if order.bonus == 0 {
+        return Ok(());
+    }
+    let (order_account, order_bump) = Pubkey::find_program_address(
+        &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
+        program_id
+    );
+    require!(order_account == order.key(), ErrorCode::InvalidOrderAccount);
+
+    let vault_authority_seeds = &[
+        b"order",
+        order.user.as_ref(),
+        order.job_hash.as_ref(),
+        &[order_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: order_vault_token_account.to_account_info(),
+                to: payer_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+        order.bonus,
+    )?;
+    order.bonus_forfeited = true;
+
+    Ok(())
+}
+
+/// Moves a delivered order's escrow to the provider vault and closes the
+/// order vault, returning its rent to the payer who funded it. The client
+/// deposit goes back to the payer unless it was forfeited.
+pub fn release_escrow<'info>(
+    order: &mut Account<'info, Order>,
+    order_vault_token_account: &Account<'info, TokenAccount>,
+    vault_token_account: &Account<'info, TokenAccount>,
+    payer: &AccountInfo<'info>,
+    payer_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let (order_account, order_bump) = Pubkey::find_program_address(
+        &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
+        program_id
+    );
+    require!(order_account == order.key(), ErrorCode::InvalidOrderAccount);
+    require_keys_eq!(
+        order_vault_token_account.owner,
+        order.key(),
+        ErrorCode::InvalidOrderVaultTokenAccountOwner
+    );
+
+    let vault_authority_seeds = &[
+        b"order",
+        order.user.as_ref(),
+        order.job_hash.as_ref(),
+        &[order_bump],
+    ];
+    let deposit_returned = if order.deposit_forfeited { 0 } else { order.client_deposit };
+    if deposit_returned > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: order_vault_token_account.to_account_info(),
+                    to: payer_token_account.to_account_info(),
+                    authority: order.to_account_info(),
+                },
+                &[vault_authority_seeds]
+            ),
+            deposit_returned,
+        )?;
+    }
+    if order.client_deposit > 0 {
+        emit!(DepositSettled {
+            order: order.key(),
+            amount: order.client_deposit,
+            forfeited: order.deposit_forfeited,
+        });
+    }
+
+    // transfer all tokens from order_vault_token_account to vault_token_account
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {  
+                from: order_vault_token_account.to_account_info(),
+                to: vault_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+        order.escrowed() - deposit_returned,
+    )?;
+
+    token::close_account(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: order_vault_token_account.to_account_info(),
+                destination: payer.clone(),
+                authority: order.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+    )?;
+
+    order.status = OrderStatus::Completed;
+
+    Ok(())
+}
+
+/// Counts a completed order in both parties' history. Orders the provider
+/// placed with itself are left out entirely.
+pub fn record_completion(
+    order: &Order,
+    reputation: &mut Reputation,
+    client_stats: &mut ClientStats,
+    score: &ScoreParams,
+    now: i64,
+) {
+    if order.is_self_dealt() {
+        return;
+    }
+    client_stats.client = order.user;
+    client_stats.orders_completed += 1;
+
+    reputation.provider = order.provider;
+    reputation.orders_completed += 1;
+    reputation.revisions += order.revisions as u64;
+    // Vesting orders are paid by the clock, so timeliness does not apply
+    let late = !order.vesting && order.completed_at >= order.deadline;
+    if late {
+        reputation.orders_late += 1;
+    }
+    reputation.volume = reputation.volume.saturating_add(order.payout());
+    reputation.record(ScoreEvent::Completed { late, volume: order.payout() }, score, now);
+    reputation.total_duration = reputation
+        .total_duration
+        .saturating_add(order.completed_at.saturating_sub(order.started_at).max(0) as u64);
+}
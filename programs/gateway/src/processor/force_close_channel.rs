@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Transfer};
+use crate::context::ForceCloseChannel;
+use crate::state::ChannelStatus;
+use crate::errors::ErrorCode;
+use crate::events::ChannelForceClosed;
+
+// The provider never settled before the timeout, so the client takes the deposit back
+pub fn process_force_close_channel(ctx: Context<ForceCloseChannel>) -> Result<()> {
+    let channel = &mut ctx.accounts.channel;
+    require!(channel.status == ChannelStatus::Open, ErrorCode::ChannelClosed);
+    require!(ctx.accounts.clock.unix_timestamp >= channel.expires_at, ErrorCode::ChannelNotExpired);
+
+    channel.status = ChannelStatus::Closed;
+
+    let channel_id = channel.channel_id.to_le_bytes();
+    let (_, channel_bump) = Pubkey::find_program_address(
+        &[b"channel", channel.client.as_ref(), channel.provider.as_ref(), channel_id.as_ref()],
+        ctx.program_id
+    );
+    let channel_seeds = &[
+        b"channel",
+        channel.client.as_ref(),
+        channel.provider.as_ref(),
+        channel_id.as_ref(),
+        &[channel_bump],
+    ];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.channel_vault_token_account.to_account_info(),
+                to: ctx.accounts.client_token_account.to_account_info(),
+                authority: channel.to_account_info(),
+            },
+            &[channel_seeds]
+        ),
+        channel.deposit,
+    )?;
+
+    token::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.channel_vault_token_account.to_account_info(),
+                destination: ctx.accounts.client.to_account_info(),
+                authority: channel.to_account_info(),
+            },
+            &[channel_seeds]
+        ),
+    )?;
+
+    emit!(ChannelForceClosed {
+        channel: channel.key(),
+        refunded: channel.deposit,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::context::GetReputation;
+use crate::errors::ErrorCode;
+use crate::state::{Reputation, ReputationView};
+
+/// Returns the provider's reputation through return data so other programs
+/// can gate on it by CPI. A provider without history gets an all-zero view.
+pub fn process_get_reputation(ctx: Context<GetReputation>) -> Result<ReputationView> {
+    let reputation_info = &ctx.accounts.reputation;
+    if reputation_info.data_is_empty() {
+        return Ok(ReputationView {
+            provider: ctx.accounts.provider.key(),
+            ..Default::default()
+        });
+    }
+    require_keys_eq!(*reputation_info.owner, crate::ID, ErrorCode::InvalidProgramId);
+    let reputation = Reputation::try_deserialize(&mut &reputation_info.try_borrow_data()?[..])?;
+    Ok(reputation.view(&ctx.accounts.config.score, Clock::get()?.unix_timestamp))
+}
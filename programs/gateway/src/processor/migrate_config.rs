@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::context::MigrateConfig;
+use crate::errors::ErrorCode;
+use crate::state::Config;
+
+/// Grows a config written by the single-provider release to the current
+/// layout. Fields were only appended since, so the existing bytes stay valid
+/// and the new ones start zeroed until the authority calls `update_config`.
+pub fn process_migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config = ctx.accounts.config.to_account_info();
+    require_keys_eq!(*config.owner, crate::ID, ErrorCode::InvalidLegacyAccount);
+    {
+        let data = config.try_borrow_data()?;
+        require!(data.len() < Config::SIZE, ErrorCode::AlreadyMigrated);
+        require!(data.starts_with(Config::DISCRIMINATOR), ErrorCode::InvalidLegacyAccount);
+        // `authority` is the first field
+        let authority = Pubkey::try_from(&data[8..40]).map_err(|_| ErrorCode::InvalidLegacyAccount)?;
+        require_keys_eq!(authority, ctx.accounts.authority.key(), anchor_lang::error::ErrorCode::ConstraintHasOne);
+    }
+
+    grow_account(
+        &config,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program,
+        Config::SIZE,
+    )
+}
+
+/// Tops `account` up to the rent of `len` bytes from `payer` and resizes it,
+/// zeroing the added bytes.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    len: usize,
+) -> Result<()> {
+    let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.realloc(len, true)?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::context::MigrateOrder;
+use crate::errors::ErrorCode;
+use crate::processor::migrate_config::grow_account;
+use crate::state::{Order, OrderStatus};
+
+/// Grows an open order written by the single-provider release to the current
+/// layout and fills in the parties that release left implicit. Settled
+/// legacy orders are never read again and stay as they are.
+pub fn process_migrate_order(ctx: Context<MigrateOrder>) -> Result<()> {
+    let info = ctx.accounts.order.to_account_info();
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidLegacyAccount);
+    {
+        let data = info.try_borrow_data()?;
+        require!(data.len() < Order::SIZE, ErrorCode::AlreadyMigrated);
+        require!(data.starts_with(Order::DISCRIMINATOR), ErrorCode::InvalidLegacyAccount);
+    }
+
+    grow_account(
+        &info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program,
+        Order::SIZE,
+    )?;
+
+    let mut order = Order::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
+
+    // That release escrowed every order for the config authority, paid by
+    // the client itself, in the mint of the order vault
+    let config = &ctx.accounts.config;
+    order.mint = ctx.accounts.mint.key();
+    order.provider = config.authority;
+    order.signer = config.authority_signer;
+    order.payer = order.user;
+
+    let mut data = info.try_borrow_mut_data()?;
+    order.try_serialize(&mut &mut data[..])
+}
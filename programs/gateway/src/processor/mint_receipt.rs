@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, Transfer};
+use anchor_spl::associated_token::{self, Create};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::ExtensionType,
+    instruction::AuthorityType,
+    state::Mint,
+};
+use anchor_spl::token_2022::{self, InitializeMint2, MintTo, SetAuthority};
+use anchor_spl::token_interface::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, token_metadata_initialize,
+    token_metadata_update_field, MetadataPointerInitialize, NonTransferableMintInitialize,
+    TokenMetadataInitialize, TokenMetadataUpdateField,
+};
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use crate::context::MintReceipt;
+use crate::errors::ErrorCode;
+use crate::events::ReceiptMinted;
+use crate::state::OrderStatus;
+
+pub const RECEIPT_NAME: &str = "Gateway receipt";
+pub const RECEIPT_SYMBOL: &str = "RECEIPT";
+
+/// Mints a non-transferable Token-2022 receipt for a settled order to both
+/// the client and the provider, at the request of the order's client or payer. The mint is a PDA of the order, so there is
+/// at most one receipt per order, and its metadata records the outcome.
+pub fn process_mint_receipt(ctx: Context<MintReceipt>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let outcome = match order.status {
+        OrderStatus::Completed => "completed",
+        OrderStatus::Refunded => "refunded",
+        OrderStatus::Aborted => "aborted",
+        _ => return err!(ErrorCode::InvalidOrderStatus),
+    };
+
+    let mint = ctx.accounts.receipt_mint.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let order_key = order.key();
+    let receipt_seeds: &[&[u8]] = &[b"receipt", order_key.as_ref(), &[ctx.bumps.receipt_mint]];
+    let signer_seeds = &[receipt_seeds];
+
+    let fields = [
+        ("order", order_key.to_string()),
+        ("job_hash", hex::encode(order.job_hash)),
+        ("result_hash", hex::encode(order.result_hash)),
+        ("outcome", outcome.to_string()),
+    ];
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(mint.key()))?,
+        mint: mint.key(),
+        name: RECEIPT_NAME.to_string(),
+        symbol: RECEIPT_SYMBOL.to_string(),
+        uri: String::new(),
+        additional_metadata: fields.iter().map(|(key, value)| (key.to_string(), value.clone())).collect(),
+    };
+    // The metadata is written after the mint is initialized, but its rent
+    // has to be in the account from the start
+    let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let lamports = Rent::get()?.minimum_balance(mint_len + metadata.tlv_size_of()?);
+
+    // Anyone can send lamports to the PDA beforehand, which would make
+    // `create_account` fail, so top it up and allocate it instead
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let missing = lamports.saturating_sub(mint.lamports());
+    if missing > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: mint.clone(),
+                },
+            ),
+            missing,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: mint.clone() },
+            signer_seeds,
+        ),
+        mint_len as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign { account_to_assign: mint.clone() },
+            signer_seeds,
+        ),
+        &token_2022::ID,
+    )?;
+    non_transferable_mint_initialize(CpiContext::new(
+        token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        },
+    ))?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(mint.key()),
+        Some(mint.key()),
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(token_program.clone(), InitializeMint2 { mint: mint.clone() }),
+        0,
+        &mint.key(),
+        None,
+    )?;
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: mint.clone(),
+                mint_authority: mint.clone(),
+                mint: mint.clone(),
+            },
+            signer_seeds,
+        ),
+        metadata.name,
+        metadata.symbol,
+        metadata.uri,
+    )?;
+    for (key, value) in fields {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataUpdateField {
+                    program_id: token_program.clone(),
+                    metadata: mint.clone(),
+                    update_authority: mint.clone(),
+                },
+                signer_seeds,
+            ),
+            Field::Key(key.to_string()),
+            value,
+        )?;
+    }
+
+    for (owner, receipt_account) in [
+        (&ctx.accounts.client, &ctx.accounts.client_receipt_account),
+        (&ctx.accounts.provider, &ctx.accounts.provider_receipt_account),
+    ] {
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: receipt_account.to_account_info(),
+                authority: owner.to_account_info(),
+                mint: mint.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                MintTo {
+                    mint: mint.clone(),
+                    to: receipt_account.to_account_info(),
+                    authority: mint.clone(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+    }
+
+    // No more receipts can be minted for this order
+    token_2022::set_authority(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            SetAuthority {
+                current_authority: mint.clone(),
+                account_or_mint: mint.clone(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    emit!(ReceiptMinted {
+        order: order_key,
+        mint: mint.key(),
+        outcome: order.status,
+    });
+
+    Ok(())
+}
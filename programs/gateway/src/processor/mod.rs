@@ -3,4 +3,43 @@ pub mod commit;
 pub mod deliver;
 pub mod decline;
 pub mod refund;
-pub mod withdraw; 
\ No newline at end of file
+pub mod withdraw;
+pub mod estimate;
+pub mod evaluate;
+pub mod commit_evaluation;
+pub mod register_provider;
+pub mod open_job_request;
+pub mod place_bid;
+pub mod accept_bid;
+pub mod finalize;
+pub mod dispute;
+pub mod update_config;
+pub mod accept_delivery;
+pub mod deliver_encrypted;
+pub mod acknowledge_ciphertext;
+pub mod reveal_key;
+pub mod top_up_credit;
+pub mod draw_credit;
+pub mod request_credit_withdrawal;
+pub mod withdraw_credit;
+pub mod open_channel;
+pub mod close_channel;
+pub mod force_close_channel;
+pub mod create_subscription;
+pub mod charge;
+pub mod cancel_subscription;
+pub mod claim_vested;
+pub mod stop_vesting;
+pub mod tip;
+pub mod amend_order;
+pub mod request_revision;
+pub mod commit_relayed;
+pub mod deposit_bond;
+pub mod withdraw_bond;
+pub mod set_mint_config;
+pub mod get_reputation;
+pub mod mint_receipt;
+pub mod set_profile;
+pub mod migrate_config;
+pub mod migrate_order;
+pub mod sweep_legacy_vault;
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::OpenChannel;
+use crate::state::ChannelStatus;
+use crate::errors::ErrorCode;
+use crate::events::ChannelOpened;
+
+pub fn process_open_channel(
+    ctx: Context<OpenChannel>,
+    channel_id: u64,
+    amount: u64,
+    timeout: i64,
+) -> Result<()> {
+    require!(timeout > 0, ErrorCode::InvalidDeadline);
+
+    let channel = &mut ctx.accounts.channel;
+    channel.client = ctx.accounts.client.key();
+    channel.provider = ctx.accounts.provider.key();
+    channel.mint = ctx.accounts.mint.key();
+    channel.channel_id = channel_id;
+    channel.deposit = amount;
+    channel.opened_at = ctx.accounts.clock.unix_timestamp;
+    channel.expires_at = channel.opened_at + timeout;
+    channel.status = ChannelStatus::Open;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.client_token_account.to_account_info(),
+                to: ctx.accounts.channel_vault_token_account.to_account_info(),
+                authority: ctx.accounts.client.to_account_info(),
+            }
+        ),
+        amount
+    )?;
+
+    emit!(ChannelOpened {
+        channel: channel.key(),
+        client: channel.client,
+        provider: channel.provider,
+        deposit: amount,
+        expires_at: channel.expires_at,
+    });
+
+    Ok(())
+}
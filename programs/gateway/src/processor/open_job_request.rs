@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::context::OpenJobRequest;
+use crate::state::JobRequestStatus;
+use crate::events::JobRequestOpened;
+
+pub fn process_open_job_request(
+    ctx: Context<OpenJobRequest>,
+    job_hash: [u8; 32],
+    budget: u64,
+    bidding_deadline: i64,
+) -> Result<()> {
+    let job_request = &mut ctx.accounts.job_request;
+    job_request.user = ctx.accounts.user.key();
+    job_request.job_hash = job_hash;
+    job_request.mint = ctx.accounts.mint.key();
+    job_request.budget = budget;
+    job_request.bidding_deadline = bidding_deadline;
+    job_request.status = JobRequestStatus::Open;
+    job_request.bid_count = 0;
+
+    emit!(JobRequestOpened {
+        job_request: job_request.key(),
+        user: job_request.user,
+        job_hash,
+        budget,
+        bidding_deadline,
+    });
+
+    Ok(())
+}
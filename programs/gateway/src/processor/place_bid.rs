@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::context::PlaceBid;
+use crate::state::JobRequestStatus;
+use crate::errors::ErrorCode;
+use crate::events::BidPlaced;
+
+pub fn process_place_bid(ctx: Context<PlaceBid>, price: u64, deadline: i64) -> Result<()> {
+    let now = ctx.accounts.clock.unix_timestamp;
+    let job_request = &mut ctx.accounts.job_request;
+    require!(job_request.status == JobRequestStatus::Open, ErrorCode::BiddingClosed);
+    require!(now < job_request.bidding_deadline, ErrorCode::BiddingClosed);
+    require!(price <= job_request.budget, ErrorCode::BudgetExceeded);
+    require!(deadline > job_request.bidding_deadline, ErrorCode::InvalidDeadline);
+
+    let bid = &mut ctx.accounts.bid;
+    bid.job_request = job_request.key();
+    bid.provider = ctx.accounts.authority.key();
+    bid.price = price;
+    bid.deadline = deadline;
+    bid.placed_at = now;
+    bid.signer = ctx.accounts.provider.signer;
+
+    job_request.bid_count += 1;
+
+    // Bids carry the provider's track record so clients compare quotes side by side
+    let reputation = &ctx.accounts.reputation;
+    emit!(BidPlaced {
+        job_request: job_request.key(),
+        bid: bid.key(),
+        provider: bid.provider,
+        price,
+        deadline,
+        orders_completed: reputation.orders_completed,
+        orders_aborted: reputation.orders_aborted,
+        orders_refunded: reputation.orders_refunded,
+        volume: reputation.volume,
+    });
+
+    Ok(())
+}
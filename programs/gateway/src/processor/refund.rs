@@ -1,14 +1,41 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, CloseAccount, Transfer};
 use crate::context::Refund;
-use crate::state::{OrderStatus};
+use crate::state::{OrderStatus, ScoreEvent};
 use crate::errors::ErrorCode;
 use crate::events::RefundEvent;
+use crate::processor::deposit_bond::{slash_bond, unlock_bond};
 
 pub fn process_refund(ctx: Context<Refund>) -> Result<()> {
     let order = &mut ctx.accounts.order;
-    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
-    require!(ctx.accounts.clock.unix_timestamp > order.deadline, ErrorCode::RefundBeforeDeadline);
+    // An encrypted delivery whose key was never revealed is refundable like a missed one,
+    // and so is a dispute the provider did not answer in time
+    require!(
+        matches!(
+            order.status,
+            OrderStatus::Started
+                | OrderStatus::Encrypted
+                | OrderStatus::Acknowledged
+                | OrderStatus::Revision
+                | OrderStatus::Disputed
+        ),
+        ErrorCode::InvalidOrderStatus
+    );
+    let refundable_after = match order.status {
+        OrderStatus::Revision => order.revision_deadline,
+        OrderStatus::Disputed => order.dispute_ends_at,
+        _ => order.deadline + order.grace_period,
+    };
+    require!(
+        ctx.accounts.clock.unix_timestamp > refundable_after,
+        ErrorCode::RefundBeforeDeadline
+    );
+    // By the deadline a vesting order still streaming has paid everything to the
+    // provider. One that moved on to a delivery is refundable like any other.
+    require!(
+        !(order.vesting && order.status == OrderStatus::Started),
+        ErrorCode::VestingOrder
+    );
 
     let (order_account, order_bump) = Pubkey::find_program_address(
         &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
@@ -39,7 +66,7 @@ pub fn process_refund(ctx: Context<Refund>) -> Result<()> {
             },
             &[vault_authority_seeds]
         ),
-        order.price,
+        order.escrowed(),
     )?;
 
     // remove vault token account
@@ -56,8 +83,38 @@ pub fn process_refund(ctx: Context<Refund>) -> Result<()> {
         ),
     )?;
 
+    // A missed deadline costs a bonded provider part of the bond
+    unlock_bond(order, ctx.accounts.bond.as_mut())?;
+    if order.bonded {
+        let bond = ctx.accounts.bond.as_mut().ok_or(ErrorCode::BondRequired)?;
+        slash_bond(
+            order,
+            bond,
+            ctx.accounts.bond_vault_token_account.as_ref(),
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            ctx.program_id,
+        )?;
+        ctx.accounts.reputation.bond = bond.amount;
+    }
+
+    let self_dealt = order.is_self_dealt();
+    // The provider left the dispute unanswered, so the client wins it
+    if !self_dealt && order.status == OrderStatus::Disputed {
+        let client_stats = &mut ctx.accounts.client_stats;
+        client_stats.client = order.user;
+        client_stats.disputes_won += 1;
+    }
+
     order.status = OrderStatus::Refunded;
     order.completed_at = ctx.accounts.clock.unix_timestamp;
+
+    if !self_dealt {
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.provider = order.provider;
+        reputation.orders_refunded += 1;
+        reputation.record(ScoreEvent::Refunded, &ctx.accounts.config.score, order.completed_at);
+    }
     
     emit!(RefundEvent {
         order: order.key(),
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::context::RegisterProvider;
+use crate::events::ProviderRegistered;
+
+pub fn process_register_provider(ctx: Context<RegisterProvider>, signer: Pubkey) -> Result<()> {
+    let provider = &mut ctx.accounts.provider;
+    provider.authority = ctx.accounts.authority.key();
+    provider.signer = signer;
+    provider.registered_at = ctx.accounts.clock.unix_timestamp;
+
+    let reputation = &mut ctx.accounts.reputation;
+    reputation.provider = provider.authority;
+
+    emit!(ProviderRegistered {
+        provider: provider.authority,
+        signer,
+    });
+
+    Ok(())
+}
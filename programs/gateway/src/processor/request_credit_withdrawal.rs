@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::context::RequestCreditWithdrawal;
+use crate::events::CreditWithdrawalRequested;
+
+// Starts the notice period during which the provider can still draw
+// receipts it already holds.
+pub fn process_request_credit_withdrawal(ctx: Context<RequestCreditWithdrawal>) -> Result<()> {
+    let credit = &mut ctx.accounts.credit;
+    credit.withdrawable_at = ctx.accounts.clock.unix_timestamp + ctx.accounts.config.credit_notice_period;
+
+    emit!(CreditWithdrawalRequested {
+        credit: credit.key(),
+        available_at: credit.withdrawable_at,
+    });
+
+    Ok(())
+}
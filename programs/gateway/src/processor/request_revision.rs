@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::context::RequestRevision;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::RevisionRequested;
+
+// Sends a delivered order back to the provider during the acceptance window.
+// The provider redelivers with `deliver` before the revision deadline.
+pub fn process_request_revision(ctx: Context<RequestRevision>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    let config = &ctx.accounts.config;
+    let now = ctx.accounts.clock.unix_timestamp;
+    require!(order.status == OrderStatus::Delivered, ErrorCode::InvalidOrderStatus);
+    require!(now < order.challenge_ends_at, ErrorCode::ChallengeWindowClosed);
+    require!(order.revisions < config.max_revisions, ErrorCode::RevisionLimitReached);
+
+    order.revisions += 1;
+    order.revision_deadline = now + config.revision_period;
+    order.status = OrderStatus::Revision;
+
+    emit!(RevisionRequested {
+        order: order.key(),
+        revision: order.revisions,
+        revision_deadline: order.revision_deadline,
+    });
+
+    Ok(())
+}
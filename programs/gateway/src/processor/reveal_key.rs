@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::context::RevealKey;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::KeyRevealed;
+use crate::processor::deliver::settle_delivery_terms;
+use crate::processor::deposit_bond::release_bond;
+use crate::processor::finalize::{record_completion, release_escrow};
+
+pub fn process_reveal_key(ctx: Context<RevealKey>, decryption_key: [u8; 32]) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Acknowledged, ErrorCode::InvalidOrderStatus);
+    let now = ctx.accounts.clock.unix_timestamp;
+    require!(now < order.deadline + order.grace_period, ErrorCode::DeliverAfterDeadline);
+    require!(
+        anchor_lang::solana_program::hash::hash(&decryption_key).to_bytes() == order.key_hash,
+        ErrorCode::KeyHashMismatch
+    );
+
+    order.decryption_key = decryption_key;
+    order.completed_at = now;
+
+    settle_delivery_terms(
+        order,
+        &ctx.accounts.order_vault_token_account,
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.token_program,
+        ctx.program_id,
+        now,
+    )?;
+    release_escrow(
+        order,
+        &ctx.accounts.order_vault_token_account,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.token_program,
+        ctx.program_id,
+    )?;
+    release_bond(order, &ctx.accounts.bond)?;
+    record_completion(
+        order,
+        &mut ctx.accounts.reputation,
+        &mut ctx.accounts.client_stats,
+        &ctx.accounts.config.score,
+        now,
+    );
+
+    emit!(KeyRevealed {
+        order: order.key(),
+        decryption_key,
+        amount: order.payout(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::context::SetMintConfig;
+use crate::errors::ErrorCode;
+use crate::events::MintConfigUpdated;
+
+pub fn process_set_mint_config(ctx: Context<SetMintConfig>, unit: u64, min_feedback_price: u64) -> Result<()> {
+    require!(unit > 0, ErrorCode::InvalidFeedbackUnit);
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.mint = ctx.accounts.mint.key();
+    mint_config.unit = unit;
+    mint_config.min_feedback_price = min_feedback_price;
+
+    emit!(MintConfigUpdated {
+        mint: mint_config.mint,
+        unit,
+        min_feedback_price,
+    });
+
+    Ok(())
+}
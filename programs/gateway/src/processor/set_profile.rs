@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::context::SetProfile;
+use crate::errors::ErrorCode;
+use crate::events::ProfileUpdated;
+use crate::state::{
+    ProfileParams, MAX_PROFILE_MINTS, MAX_PROFILE_NAME_LEN, MAX_PROFILE_TAGS, MAX_PROFILE_TAG_LEN,
+    MAX_PROFILE_URL_LEN,
+};
+
+/// Creates or replaces the provider profile of a registered provider or of
+/// the deployment authority
+pub fn process_set_profile(ctx: Context<SetProfile>, params: ProfileParams) -> Result<()> {
+    require!(params.name.len() <= MAX_PROFILE_NAME_LEN, ErrorCode::ProfileFieldTooLong);
+    require!(
+        params.tags.len() <= MAX_PROFILE_TAGS
+            && params.tags.iter().all(|tag| tag.len() <= MAX_PROFILE_TAG_LEN),
+        ErrorCode::ProfileFieldTooLong
+    );
+    require!(params.metadata_uri.len() <= MAX_PROFILE_URL_LEN, ErrorCode::ProfileFieldTooLong);
+    require!(params.quote_endpoint.len() <= MAX_PROFILE_URL_LEN, ErrorCode::ProfileFieldTooLong);
+    require!(params.accepted_mints.len() <= MAX_PROFILE_MINTS, ErrorCode::ProfileFieldTooLong);
+
+    // The listed signer is the key offers are checked against, never a free-form value
+    let signer = match &ctx.accounts.provider {
+        Some(provider) => provider.signer,
+        None => {
+            let config = &ctx.accounts.config;
+            require_keys_eq!(ctx.accounts.authority.key(), config.authority, ErrorCode::InvalidProvider);
+            config.authority_signer
+        }
+    };
+
+    let profile = &mut ctx.accounts.profile;
+    profile.authority = ctx.accounts.authority.key();
+    profile.signer = signer;
+    profile.name = params.name;
+    profile.tags = params.tags;
+    profile.metadata_uri = params.metadata_uri;
+    profile.quote_endpoint = params.quote_endpoint;
+    profile.accepted_mints = params.accepted_mints;
+    profile.updated_at = ctx.accounts.clock.unix_timestamp;
+
+    emit!(ProfileUpdated {
+        provider: profile.authority,
+        signer: profile.signer,
+        name: profile.name.clone(),
+        tags: profile.tags.clone(),
+        metadata_uri: profile.metadata_uri.clone(),
+        quote_endpoint: profile.quote_endpoint.clone(),
+        accepted_mints: profile.accepted_mints.clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Transfer};
+use crate::context::StopVesting;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::{Abort, VestingStopped};
+use crate::processor::deposit_bond::unlock_bond;
+
+// Client-side counterpart of `decline` for vesting orders: the provider keeps
+// what has vested so far and the unvested remainder goes back to the client.
+pub fn process_stop_vesting(ctx: Context<StopVesting>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.vesting, ErrorCode::NotVestingOrder);
+    require!(order.status == OrderStatus::Started, ErrorCode::InvalidOrderStatus);
+
+    let (order_account, order_bump) = Pubkey::find_program_address(
+        &[b"order", order.user.as_ref(), order.job_hash.as_ref()],
+        ctx.program_id
+    );
+    require!(order_account == order.key(), ErrorCode::InvalidOrderAccount);
+    require_keys_eq!(
+        ctx.accounts.order_vault_token_account.owner,
+        order.key(),
+        ErrorCode::InvalidOrderVaultTokenAccountOwner
+    );
+
+    let now = ctx.accounts.clock.unix_timestamp;
+    // Once fully vested the order completes through `finalize`, so a stop is
+    // always an early cancellation by the client
+    require!(now < order.deadline, ErrorCode::VestingComplete);
+    let vested = order.vested(now);
+    let paid = vested.saturating_sub(order.vested_claimed);
+    let refunded = order.escrowed() - paid;
+
+    let vault_authority_seeds = &[
+        b"order",
+        order.user.as_ref(),
+        order.job_hash.as_ref(),
+        &[order_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.order_vault_token_account.to_account_info(),
+                to: ctx.accounts.vault_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+        paid,
+    )?;
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.order_vault_token_account.to_account_info(),
+                to: ctx.accounts.payer_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+        refunded,
+    )?;
+
+    token::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.order_vault_token_account.to_account_info(),
+                destination: ctx.accounts.payer.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+    )?;
+
+    unlock_bond(order, ctx.accounts.bond.as_mut())?;
+
+    if !order.is_self_dealt() {
+        let client_stats = &mut ctx.accounts.client_stats;
+        client_stats.client = order.user;
+        client_stats.cancellations += 1;
+    }
+
+    order.vested_claimed += paid;
+    order.status = OrderStatus::Aborted;
+    order.completed_at = now;
+
+    emit!(VestingStopped {
+        order: order.key(),
+        paid,
+        refunded,
+    });
+
+    emit!(Abort {
+        order: order.key(),
+    });
+
+    Ok(())
+}
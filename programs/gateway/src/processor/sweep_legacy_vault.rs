@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Transfer};
+use crate::context::SweepLegacyVault;
+
+/// Moves what the single-provider release earned into the authority's
+/// per-provider vault and closes the old `["vault", mint]` account.
+pub fn process_sweep_legacy_vault(ctx: Context<SweepLegacyVault>) -> Result<()> {
+    let vault_authority_seeds: &[&[u8]] = &[b"vault-authority", &[ctx.bumps.vault_authority]];
+    let amount = ctx.accounts.legacy_vault_token_account.amount;
+
+    if amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.legacy_vault_token_account.to_account_info(),
+                    to: ctx.accounts.vault_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &[vault_authority_seeds]
+            ),
+            amount,
+        )?;
+    }
+
+    token::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.legacy_vault_token_account.to_account_info(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            &[vault_authority_seeds]
+        ),
+    )?;
+
+    Ok(())
+}
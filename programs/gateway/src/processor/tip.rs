@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::Tip;
+use crate::state::{OrderStatus};
+use crate::errors::ErrorCode;
+use crate::events::Tipped;
+
+pub fn process_tip(ctx: Context<Tip>, amount: u64) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == OrderStatus::Completed, ErrorCode::InvalidOrderStatus);
+    require!(amount > 0, ErrorCode::InsufficientFunds);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.vault_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            }
+        ),
+        amount
+    )?;
+
+    order.tipped = order.tipped.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    if !order.is_self_dealt() {
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.provider = order.provider;
+        reputation.tips_count += 1;
+        reputation.tips_volume = reputation.tips_volume.saturating_add(amount);
+    }
+
+    emit!(Tipped {
+        order: order.key(),
+        provider: order.provider,
+        amount,
+    });
+
+    Ok(())
+}
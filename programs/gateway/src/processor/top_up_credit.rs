@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::TopUpCredit;
+use crate::events::CreditToppedUp;
+
+pub fn process_top_up_credit(ctx: Context<TopUpCredit>, amount: u64) -> Result<()> {
+    let credit = &mut ctx.accounts.credit;
+    credit.client = ctx.accounts.client.key();
+    credit.provider = ctx.accounts.provider.key();
+    credit.mint = ctx.accounts.mint.key();
+    credit.deposited += amount;
+    // Topping up means the client keeps using the credit
+    credit.withdrawable_at = 0;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.client_token_account.to_account_info(),
+                to: ctx.accounts.credit_vault_token_account.to_account_info(),
+                authority: ctx.accounts.client.to_account_info(),
+            }
+        ),
+        amount
+    )?;
+
+    emit!(CreditToppedUp {
+        credit: credit.key(),
+        amount,
+        deposited: credit.deposited,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::context::UpdateConfig;
+use crate::state::ConfigParams;
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+
+pub fn process_update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    require!(params.challenge_window >= 0, ErrorCode::InvalidDeadline);
+    require!(params.credit_notice_period >= 0, ErrorCode::InvalidDeadline);
+    require!(params.revision_period >= 0, ErrorCode::InvalidDeadline);
+    require!(params.bond_slash_bps <= 10_000, ErrorCode::InvalidBasisPoints);
+    require!(params.score.half_life >= 0, ErrorCode::InvalidDeadline);
+    require!(params.dispute_period >= 0, ErrorCode::InvalidDeadline);
+    // Otherwise a disputed delivery would be refundable right away
+    require!(
+        params.challenge_window == 0 || params.dispute_period > 0,
+        ErrorCode::DisputePeriodRequired
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.challenge_window = params.challenge_window;
+    config.credit_notice_period = params.credit_notice_period;
+    config.max_revisions = params.max_revisions;
+    config.revision_period = params.revision_period;
+    config.bond_slash_bps = params.bond_slash_bps;
+    config.bond_leverage = params.bond_leverage;
+    config.score = params.score;
+    config.dispute_period = params.dispute_period;
+
+    emit!(ConfigUpdated {
+        challenge_window: config.challenge_window,
+        credit_notice_period: config.credit_notice_period,
+        max_revisions: config.max_revisions,
+        revision_period: config.revision_period,
+        bond_slash_bps: config.bond_slash_bps,
+        bond_leverage: config.bond_leverage,
+        score: config.score,
+        dispute_period: config.dispute_period,
+    });
+
+    Ok(())
+}
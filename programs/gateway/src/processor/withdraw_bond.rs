@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::WithdrawBond;
+use crate::errors::ErrorCode;
+use crate::events::BondWithdrawn;
+
+pub fn process_withdraw_bond(ctx: Context<WithdrawBond>, amount: u64) -> Result<()> {
+    let bond = &mut ctx.accounts.bond;
+    let remaining = bond.amount.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+    bond.amount = remaining;
+    // Whatever is left must still back the work in progress
+    require!(
+        bond.outstanding <= bond.capacity(ctx.accounts.config.bond_leverage),
+        ErrorCode::BondLocked
+    );
+
+    let (_, bond_bump) = Pubkey::find_program_address(
+        &[b"bond", bond.provider.as_ref(), bond.mint.as_ref()],
+        ctx.program_id
+    );
+    let bond_seeds = &[
+        b"bond",
+        bond.provider.as_ref(),
+        bond.mint.as_ref(),
+        &[bond_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bond_vault_token_account.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: bond.to_account_info(),
+            },
+            &[bond_seeds]
+        ),
+        amount,
+    )?;
+
+    let reputation = &mut ctx.accounts.reputation;
+    reputation.provider = bond.provider;
+    reputation.bond = bond.amount;
+
+    emit!(BondWithdrawn {
+        provider: bond.provider,
+        mint: bond.mint,
+        amount,
+        total: bond.amount,
+    });
+
+    Ok(())
+}
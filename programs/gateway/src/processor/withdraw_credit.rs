@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::context::WithdrawCredit;
+use crate::errors::ErrorCode;
+use crate::events::CreditWithdrawn;
+
+pub fn process_withdraw_credit(ctx: Context<WithdrawCredit>) -> Result<()> {
+    let credit = &mut ctx.accounts.credit;
+    require!(credit.withdrawable_at != 0, ErrorCode::WithdrawalNotRequested);
+    require!(ctx.accounts.clock.unix_timestamp >= credit.withdrawable_at, ErrorCode::NoticePeriodNotOver);
+
+    let amount = credit.deposited - credit.drawn;
+    credit.deposited = credit.drawn;
+    credit.withdrawable_at = 0;
+
+    let (_, credit_bump) = Pubkey::find_program_address(
+        &[b"credit", credit.client.as_ref(), credit.provider.as_ref(), credit.mint.as_ref()],
+        ctx.program_id
+    );
+    let credit_seeds = &[
+        b"credit",
+        credit.client.as_ref(),
+        credit.provider.as_ref(),
+        credit.mint.as_ref(),
+        &[credit_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.credit_vault_token_account.to_account_info(),
+                to: ctx.accounts.client_token_account.to_account_info(),
+                authority: credit.to_account_info(),
+            },
+            &[credit_seeds]
+        ),
+        amount,
+    )?;
+
+    emit!(CreditWithdrawn {
+        credit: credit.key(),
+        amount,
+    });
+
+    Ok(())
+}
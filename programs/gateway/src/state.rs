@@ -6,8 +6,23 @@ pub enum OrderStatus {
     Completed,
     Aborted,
     Refunded,
+    Requested,
+    Evaluated,
+    Delivered,
+    Disputed,
+    Encrypted,
+    Acknowledged,
+    Revision,
 }
 
+pub const BPS_DENOMINATOR: u128 = 10_000;
+pub const SECONDS_PER_HOUR: u128 = 3_600;
+// Weight of one event in the decayed reputation history
+pub const SCORE_UNIT: u64 = 1_000_000;
+pub const MAX_RATING: u8 = 5;
+// Ratings at or below this count against the client as unsatisfied verdicts
+pub const UNSATISFIED_RATING: u8 = 2;
+
 #[account]
 pub struct Order {
     pub user: Pubkey,
@@ -19,10 +34,89 @@ pub struct Order {
     pub status: OrderStatus,
     pub started_at: i64,
     pub completed_at: i64,
+    pub mint: Pubkey,
+    pub budget: u64,
+    pub provider: Pubkey,
+    pub challenge_ends_at: i64,
+    pub accepted_at: i64,
+    // 1..=5, or 0 when the client accepted without rating
+    pub rating: u8,
+    // Fair-exchange delivery: sha256 of the decryption key, then the key itself once revealed
+    pub key_hash: [u8; 32],
+    pub decryption_key: [u8; 32],
+    // Escrow streams to the provider linearly between started_at and deadline
+    pub vesting: bool,
+    pub vested_claimed: u64,
+    // Deliveries up to deadline + grace_period are accepted at a reduced price
+    pub grace_period: i64,
+    pub penalty_bps_per_hour: u16,
+    pub late_penalty: u64,
+    // Escrowed on top of the price, paid only for delivery before bonus_deadline
+    pub bonus: u64,
+    pub bonus_deadline: i64,
+    pub bonus_earned: bool,
+    pub bonus_forfeited: bool,
+    pub tipped: u64,
+    // Key that signed the offer, amendments must come from it too
+    pub signer: Pubkey,
+    pub amendments: u32,
+    pub revisions: u8,
+    pub revision_deadline: i64,
+    // Funded the escrow and receives refunds; `user` is the beneficiary
+    pub payer: Pubkey,
+    // Backed by the provider's bond, `bond_locked` of its capacity is held until the order is final
+    pub bonded: bool,
+    pub bond_slash_bps: u16,
+    pub bond_locked: u64,
+    // Anti-spam deposit escrowed with the price, lost by disputing a good delivery
+    pub client_deposit: u64,
+    pub deposit_forfeited: bool,
+    // A dispute the provider leaves unanswered until then is refundable
+    pub dispute_ends_at: i64,
 }
 
 impl Order {
     pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+
+    /// What the provider is paid in total for this order
+    pub fn payout(&self) -> u64 {
+        let bonus = if self.bonus_earned { self.bonus } else { 0 };
+        self.price - self.late_penalty + bonus
+    }
+
+    /// Tokens still held in the order vault
+    pub fn escrowed(&self) -> u64 {
+        let bonus = if self.bonus_forfeited { 0 } else { self.bonus };
+        self.price - self.late_penalty + bonus - self.vested_claimed + self.client_deposit
+    }
+
+    /// Price reduction for a delivery at `now`, capped at what is still escrowed
+    pub fn penalty_at(&self, now: i64) -> u64 {
+        if now < self.deadline {
+            return 0;
+        }
+        let late_by = (now - self.deadline) as u128;
+        let penalty = self.price as u128 * self.penalty_bps_per_hour as u128 * late_by
+            / (BPS_DENOMINATOR * SECONDS_PER_HOUR);
+        penalty.min((self.price - self.vested_claimed) as u128) as u64
+    }
+
+    /// Portion of the price vested to the provider at `now`
+    pub fn vested(&self, now: i64) -> u64 {
+        let duration = self.deadline - self.started_at;
+        if duration <= 0 || now >= self.deadline {
+            return self.price;
+        }
+        let elapsed = (now - self.started_at).max(0);
+        (self.price as u128 * elapsed as u128 / duration as u128) as u64
+    }
+
+    /// Whether the client side of the order is the provider itself
+    pub fn is_self_dealt(&self) -> bool {
+        [self.user, self.payer]
+            .iter()
+            .any(|key| *key == self.provider || *key == self.signer)
+    }
 }
 
 #[account]
@@ -30,9 +124,505 @@ pub struct Config {
     pub authority: Pubkey,
     pub authority_signer: Pubkey,
     pub program_fee_recipient: Pubkey,
+    pub challenge_window: i64,
+    pub credit_notice_period: i64,
+    pub max_revisions: u8,
+    pub revision_period: i64,
+    // Share of a provider's bond slashed per refund or lost dispute
+    pub bond_slash_bps: u16,
+    // Outstanding work a bond can back, as a multiple of its size
+    pub bond_leverage: u16,
+    pub score: ScoreParams,
+    // Time the provider has to answer a dispute before the client may refund
+    pub dispute_period: i64,
 }
 
 impl Config {
     pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub challenge_window: i64,
+    pub credit_notice_period: i64,
+    pub max_revisions: u8,
+    pub revision_period: i64,
+    pub bond_slash_bps: u16,
+    pub bond_leverage: u16,
+    pub score: ScoreParams,
+    pub dispute_period: i64,
+}
+
+/// Tuning of the provider reputation score. The score is the weighted
+/// average, in basis points, of the completion rate, the on-time rate, the
+/// average rating, the absence of refunds and the volume relative to
+/// `volume_target`. Components without any history are left out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ScoreParams {
+    // Seconds for past activity to lose half its weight, 0 disables decay
+    pub half_life: i64,
+    pub completion_weight: u16,
+    pub timeliness_weight: u16,
+    pub satisfaction_weight: u16,
+    pub refund_weight: u16,
+    pub volume_weight: u16,
+    pub volume_target: u64,
+}
+
+#[account]
+pub struct Provider {
+    pub authority: Pubkey,
+    pub signer: Pubkey,
+    pub registered_at: i64,
+}
+
+impl Provider {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+}
+
+/// Per-provider history at `["reputation", provider]`. Fields are only ever
+/// appended, so programs reading the account directly can rely on these
+/// offsets, counted from the start of the account data:
+///
+/// | offset | field                  | type   |
+/// |--------|------------------------|--------|
+/// | 0      | discriminator          | [u8; 8]|
+/// | 8      | provider               | Pubkey |
+/// | 40     | orders_completed       | u64    |
+/// | 48     | orders_aborted         | u64    |
+/// | 56     | orders_refunded        | u64    |
+/// | 64     | orders_disputed        | u64    |
+/// | 72     | orders_accepted        | u64    |
+/// | 80     | ratings_count          | u64    |
+/// | 88     | ratings_sum            | u64    |
+/// | 96     | volume                 | u64    |
+/// | 104    | orders_late            | u64    |
+/// | 112    | tips_count             | u64    |
+/// | 120    | tips_volume            | u64    |
+/// | 128    | revisions              | u64    |
+/// | 136    | bond                   | u64    |
+/// | 144    | total_duration         | u64    |
+/// | 152    | score                  | u16    |
+/// | 154    | score_updated_at       | i64    |
+/// | 162    | decayed_completed      | u64    |
+/// | 170    | decayed_late           | u64    |
+/// | 178    | decayed_aborted        | u64    |
+/// | 186    | decayed_refunded       | u64    |
+/// | 194    | decayed_ratings_count  | u64    |
+/// | 202    | decayed_ratings_sum    | u64    |
+/// | 210    | decayed_volume         | u64    |
+/// | 218    | weighted_ratings_count | u64    |
+/// | 226    | weighted_ratings_sum   | u64    |
+///
+/// Integers are little-endian. The stored `score` is only refreshed by
+/// lifecycle instructions, `get_reputation` returns it decayed to now.
+#[account]
+pub struct Reputation {
+    pub provider: Pubkey,
+    pub orders_completed: u64,
+    pub orders_aborted: u64,
+    pub orders_refunded: u64,
+    pub orders_disputed: u64,
+    pub orders_accepted: u64,
+    pub ratings_count: u64,
+    pub ratings_sum: u64,
+    pub volume: u64,
+    pub orders_late: u64,
+    pub tips_count: u64,
+    pub tips_volume: u64,
+    // Revision rounds summed over completed orders, divide by orders_completed for the average
+    pub revisions: u64,
+    pub bond: u64,
+    // Seconds from start to completion, summed over completed orders and settled channels
+    pub total_duration: u64,
+    // Score in basis points as of `score_updated_at`, see `ScoreParams`
+    pub score: u16,
+    pub score_updated_at: i64,
+    // History behind the score, in `SCORE_UNIT`s per order and decayed over
+    // time. Ratings count with their feedback weight instead.
+    pub decayed_completed: u64,
+    pub decayed_late: u64,
+    pub decayed_aborted: u64,
+    pub decayed_refunded: u64,
+    pub decayed_ratings_count: u64,
+    pub decayed_ratings_sum: u64,
+    pub decayed_volume: u64,
+    // Ratings weighted by order price, see `MintConfig::feedback_weight`
+    pub weighted_ratings_count: u64,
+    pub weighted_ratings_sum: u64,
+}
+
+/// Returned by `get_reputation` through return data, borsh encoded with no
+/// variable-length fields. Fields are only ever appended.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ReputationView {
+    pub provider: Pubkey,
+    pub orders_completed: u64,
+    pub orders_aborted: u64,
+    pub orders_refunded: u64,
+    pub orders_disputed: u64,
+    pub orders_late: u64,
+    pub ratings_count: u64,
+    pub ratings_sum: u64,
+    pub weighted_ratings_count: u64,
+    pub weighted_ratings_sum: u64,
+    pub volume: u64,
+    pub bond: u64,
+    // Completed over completed, aborted and refunded orders
+    pub completion_rate_bps: u16,
+    pub score: u16,
+}
+
+pub enum ScoreEvent {
+    Completed { late: bool, volume: u64 },
+    Aborted,
+    Refunded,
+    Rated { rating: u8, weight: u64 },
+}
+
+impl Reputation {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+
+    /// Ages the score history to `now`, adds `event` and recomputes the score
+    pub fn record(&mut self, event: ScoreEvent, params: &ScoreParams, now: i64) {
+        self.decay(params.half_life, now);
+        match event {
+            ScoreEvent::Completed { late, volume } => {
+                self.decayed_completed += SCORE_UNIT;
+                if late {
+                    self.decayed_late += SCORE_UNIT;
+                }
+                self.decayed_volume = self.decayed_volume.saturating_add(volume);
+            }
+            ScoreEvent::Aborted => self.decayed_aborted += SCORE_UNIT,
+            ScoreEvent::Refunded => self.decayed_refunded += SCORE_UNIT,
+            ScoreEvent::Rated { rating, weight } => {
+                self.decayed_ratings_count = self.decayed_ratings_count.saturating_add(weight);
+                self.decayed_ratings_sum = self
+                    .decayed_ratings_sum
+                    .saturating_add(weight.saturating_mul(rating as u64));
+            }
+        }
+        self.score = self.compute_score(params);
+    }
+
+    pub fn view(&self, params: &ScoreParams, now: i64) -> ReputationView {
+        let finished = self.orders_completed + self.orders_aborted + self.orders_refunded;
+        ReputationView {
+            provider: self.provider,
+            orders_completed: self.orders_completed,
+            orders_aborted: self.orders_aborted,
+            orders_refunded: self.orders_refunded,
+            orders_disputed: self.orders_disputed,
+            orders_late: self.orders_late,
+            ratings_count: self.ratings_count,
+            ratings_sum: self.ratings_sum,
+            weighted_ratings_count: self.weighted_ratings_count,
+            weighted_ratings_sum: self.weighted_ratings_sum,
+            volume: self.volume,
+            bond: self.bond,
+            completion_rate_bps: if finished == 0 { 0 } else { ratio_bps(self.orders_completed, finished) as u16 },
+            score: self.score_at(params, now),
+        }
+    }
+
+    /// The score as it stands at `now`, without recording anything
+    pub fn score_at(&self, params: &ScoreParams, now: i64) -> u16 {
+        let mut aged = self.clone();
+        aged.decay(params.half_life, now);
+        aged.compute_score(params)
+    }
+
+    fn decay(&mut self, half_life: i64, now: i64) {
+        let elapsed = now - self.score_updated_at;
+        self.score_updated_at = now;
+        if half_life <= 0 || elapsed <= 0 {
+            return;
+        }
+        for value in [
+            &mut self.decayed_completed,
+            &mut self.decayed_late,
+            &mut self.decayed_aborted,
+            &mut self.decayed_refunded,
+            &mut self.decayed_ratings_count,
+            &mut self.decayed_ratings_sum,
+            &mut self.decayed_volume,
+        ] {
+            *value = decay(*value, elapsed, half_life);
+        }
+    }
+
+    fn compute_score(&self, params: &ScoreParams) -> u16 {
+        let bps = BPS_DENOMINATOR as u64;
+        let finished = self.decayed_completed + self.decayed_aborted + self.decayed_refunded;
+        let mut components = Vec::with_capacity(5);
+        if finished > 0 {
+            components.push((params.completion_weight, ratio_bps(self.decayed_completed, finished)));
+            components.push((params.refund_weight, bps - ratio_bps(self.decayed_refunded, finished)));
+        }
+        if self.decayed_completed > 0 {
+            components.push((params.timeliness_weight, bps - ratio_bps(self.decayed_late, self.decayed_completed)));
+        }
+        if self.decayed_ratings_count > 0 {
+            // Weighted counts times the top rating can exceed a u64
+            let best = self.decayed_ratings_count as u128 * MAX_RATING as u128;
+            components.push((
+                params.satisfaction_weight,
+                (self.decayed_ratings_sum as u128 * BPS_DENOMINATOR / best) as u64,
+            ));
+        }
+        if params.volume_target > 0 {
+            components.push((
+                params.volume_weight,
+                ratio_bps(self.decayed_volume.min(params.volume_target), params.volume_target),
+            ));
+        }
+
+        let total_weight: u64 = components.iter().map(|(weight, _)| *weight as u64).sum();
+        if total_weight == 0 {
+            return 0;
+        }
+        let weighted: u64 = components.iter().map(|(weight, value)| *weight as u64 * value).sum();
+        (weighted / total_weight) as u16
+    }
+}
+
+/// Halves `value` every `half_life` seconds, interpolating linearly in between
+fn decay(value: u64, elapsed: i64, half_life: i64) -> u64 {
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return 0;
+    }
+    let halved = value >> halvings;
+    let remainder = (elapsed % half_life) as u128;
+    halved - (halved as u128 * remainder / (2 * half_life as u128)) as u64
+}
+
+fn ratio_bps(part: u64, whole: u64) -> u64 {
+    (part as u128 * BPS_DENOMINATOR / whole as u128) as u64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum JobRequestStatus {
+    Open,
+    Awarded,
+}
+
+#[account]
+pub struct JobRequest {
+    pub user: Pubkey,
+    pub job_hash: [u8; 32],
+    pub mint: Pubkey,
+    pub budget: u64,
+    pub bidding_deadline: i64,
+    pub status: JobRequestStatus,
+    pub bid_count: u32,
+}
+
+impl JobRequest {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+}
+
+#[account]
+pub struct Bid {
+    pub job_request: Pubkey,
+    pub provider: Pubkey,
+    pub price: u64,
+    pub deadline: i64,
+    pub placed_at: i64,
+    pub signer: Pubkey,
+}
+
+impl Bid {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+}
+
+#[account]
+pub struct CreditAccount {
+    pub client: Pubkey,
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64,
+    // Cumulative amount drawn by the provider and the counter of the last receipt used
+    pub drawn: u64,
+    pub counter: u64,
+    // End of the notice period after the client asked to withdraw, 0 if not requested
+    pub withdrawable_at: i64,
+}
+
+impl CreditAccount {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelStatus {
+    Open,
+    Closed,
+}
+
+#[account]
+pub struct Channel {
+    pub client: Pubkey,
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub channel_id: u64,
+    pub deposit: u64,
+    pub settled: u64,
+    pub opened_at: i64,
+    pub expires_at: i64,
+    pub status: ChannelStatus,
+}
+
+impl Channel {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionFunding {
+    // The whole plan is escrowed in the subscription vault up front
+    Prefunded,
+    // Each period is pulled from the client's token account through a delegate approval.
+    // A token account has a single delegate, so it backs one delegated subscription
+    // or relayed commit at a time: neither flow replaces another's approval.
+    Delegated,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionStatus {
+    Active,
+    Cancelled,
+}
+
+#[account]
+pub struct Subscription {
+    pub client: Pubkey,
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub subscription_id: u64,
+    pub period: i64,
+    pub price_per_period: u64,
+    pub periods: u32,
+    pub periods_charged: u32,
+    pub started_at: i64,
+    pub funding: SubscriptionFunding,
+    pub status: SubscriptionStatus,
+    pub last_result_hash: [u8; 32],
+}
+
+impl Subscription {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+}
+
+#[account]
+pub struct Bond {
+    pub provider: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    // Price of bonded orders not yet final
+    pub outstanding: u64,
+}
+
+impl Bond {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+
+    /// Outstanding work this bond can back
+    pub fn capacity(&self, leverage: u16) -> u64 {
+        self.amount.saturating_mul(leverage.max(1) as u64)
+    }
+}
+
+#[account]
+pub struct ClientStats {
+    pub client: Pubkey,
+    pub orders_placed: u64,
+    pub orders_completed: u64,
+    pub disputes_opened: u64,
+    // Provider conceded with `decline`
+    pub disputes_won: u64,
+    // Client conceded with `accept_delivery`
+    pub disputes_lost: u64,
+    pub unsatisfied: u64,
+    pub cancellations: u64,
+}
+
+impl ClientStats {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+
+    /// Share of completed orders the client rated unsatisfied, in basis points
+    pub fn unsatisfied_bps(&self) -> u64 {
+        if self.orders_completed == 0 {
+            return 0;
+        }
+        self.unsatisfied * BPS_DENOMINATOR as u64 / self.orders_completed
+    }
+}
+
+/// How feedback on orders paid in `mint` is weighted
+#[account]
+pub struct MintConfig {
+    pub mint: Pubkey,
+    // Price worth one `SCORE_UNIT` of feedback weight
+    pub unit: u64,
+    // Orders priced below this leave feedback that does not count
+    pub min_feedback_price: u64,
+}
+
+impl MintConfig {
+    pub const SIZE: usize = core::mem::size_of::<Self>() + Self::DISCRIMINATOR.len();
+
+    /// Weight of the feedback left on `order`. Without a mint config one
+    /// whole token weighs one `SCORE_UNIT` and there is no minimum price.
+    pub fn feedback_weight(mint_config: Option<&Self>, order: &Order, decimals: u8) -> u64 {
+        if order.is_self_dealt() {
+            return 0;
+        }
+        let (unit, min_price) = match mint_config {
+            Some(mint_config) => (mint_config.unit, mint_config.min_feedback_price),
+            None => (10u64.checked_pow(decimals as u32).unwrap_or(u64::MAX), 0),
+        };
+        if unit == 0 || order.price < min_price {
+            return 0;
+        }
+        (order.price as u128 * SCORE_UNIT as u128 / unit as u128).min(u64::MAX as u128) as u64
+    }
+}
+
+pub const MAX_PROFILE_NAME_LEN: usize = 32;
+pub const MAX_PROFILE_TAGS: usize = 8;
+pub const MAX_PROFILE_TAG_LEN: usize = 16;
+pub const MAX_PROFILE_URL_LEN: usize = 200;
+pub const MAX_PROFILE_MINTS: usize = 8;
+
+/// Public listing of a provider at `["profile", authority]`, so clients can
+/// find where to ask for quotes without an off-chain directory
+#[account]
+#[derive(InitSpace)]
+pub struct ProviderProfile {
+    pub authority: Pubkey,
+    // Key the provider signs offers with, copied from `Provider` or `Config`
+    pub signer: Pubkey,
+    #[max_len(MAX_PROFILE_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_PROFILE_TAGS, MAX_PROFILE_TAG_LEN)]
+    pub tags: Vec<String>,
+    #[max_len(MAX_PROFILE_URL_LEN)]
+    pub metadata_uri: String,
+    #[max_len(MAX_PROFILE_URL_LEN)]
+    pub quote_endpoint: String,
+    #[max_len(MAX_PROFILE_MINTS)]
+    pub accepted_mints: Vec<Pubkey>,
+    pub updated_at: i64,
+}
+
+impl ProviderProfile {
+    pub const SIZE: usize = Self::INIT_SPACE + Self::DISCRIMINATOR.len();
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProfileParams {
+    pub name: String,
+    pub tags: Vec<String>,
+    pub metadata_uri: String,
+    pub quote_endpoint: String,
+    pub accepted_mints: Vec<Pubkey>,
+}
@@ -1,4 +1,7 @@
-use hex;
+use anchor_lang::{prelude::*, solana_program};
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+use crate::errors::ErrorCode;
+
 
 pub fn check_ed25519_data(data: &[u8]) -> (Vec<u8>, Vec<u8>) {
     // According to this layout used by the Ed25519Program
@@ -17,4 +20,29 @@ pub fn check_ed25519_data(data: &[u8]) -> (Vec<u8>, Vec<u8>) {
     let msg_clone = data_msg.to_vec();
 
     (pubkey_clone, msg_clone)
-}
\ No newline at end of file
+}
+
+/// Loads the Ed25519Program instruction at `index` of the current transaction
+/// and returns the message it verified, provided `signer` signed it.
+pub fn load_ed25519_message(instructions: &AccountInfo, index: usize, signer: &Pubkey) -> Result<Vec<u8>> {
+    let ix = load_instruction_at_checked(index, instructions)?;
+    require_keys_eq!(ix.program_id, solana_program::ed25519_program::id(), ErrorCode::InvalidProgramId);
+
+    // A single signature with all offsets pointing into this instruction,
+    // so the pubkey and message read below are the ones actually verified
+    let data = &ix.data;
+    require!(data.len() > 112 && data[0] == 1, ErrorCode::InvalidInstructionData);
+    let offsets: Vec<u16> = data[2..16]
+        .chunks(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .collect();
+    require!(
+        offsets == [48, u16::MAX, 16, u16::MAX, 112, (data.len() - 112) as u16, u16::MAX],
+        ErrorCode::InvalidInstructionData
+    );
+
+    let (key, message) = check_ed25519_data(data);
+    require!(key == signer.to_bytes(), ErrorCode::InvalidSignature);
+
+    Ok(message)
+}
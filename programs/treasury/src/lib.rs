@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use gateway::cpi::accounts::{Commit, Refund};
+use gateway::program::Gateway;
+
+declare_id!("E3pExfbNG1qgcULEDPnBVCLCZMtrfX1adtF7FSqdNBYc");
+
+/// Example of a program acting as a gateway client. Orders are placed and
+/// refunded by a treasury PDA signing through `invoke_signed`. The treasury
+/// holds no data, so it can pay rent for the accounts the gateway creates.
+#[program]
+pub mod treasury {
+    use super::*;
+
+    pub fn place_order(ctx: Context<PlaceOrder>, job_hash: [u8; 32]) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let treasury_seeds: &[&[u8]] = &[b"treasury", authority.as_ref(), &[ctx.bumps.treasury]];
+
+        // The signed offer must be the transaction's first instruction, the
+        // gateway reads it from the instructions sysvar
+        gateway::cpi::commit(
+            CpiContext::new_with_signer(
+                ctx.accounts.gateway_program.to_account_info(),
+                Commit {
+                    user: ctx.accounts.treasury.to_account_info(),
+                    beneficiary: ctx.accounts.treasury.to_account_info(),
+                    instructions: ctx.accounts.instructions.to_account_info(),
+                    order: ctx.accounts.order.to_account_info(),
+                    user_token_account: ctx.accounts.treasury_token_account.to_account_info(),
+                    order_vault_token_account: ctx.accounts.order_vault_token_account.to_account_info(),
+                    client_stats: ctx.accounts.client_stats.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    bond: ctx.accounts.bond.to_account_info(),
+                    config: ctx.accounts.config.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            job_hash,
+        )
+    }
+
+    pub fn refund_order(ctx: Context<RefundOrder>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let treasury_seeds: &[&[u8]] = &[b"treasury", authority.as_ref(), &[ctx.bumps.treasury]];
+
+        gateway::cpi::refund(CpiContext::new_with_signer(
+            ctx.accounts.gateway_program.to_account_info(),
+            Refund {
+                user: ctx.accounts.treasury.to_account_info(),
+                order: ctx.accounts.order.to_account_info(),
+                user_token_account: ctx.accounts.treasury_token_account.to_account_info(),
+                order_vault_token_account: ctx.accounts.order_vault_token_account.to_account_info(),
+                reputation: ctx.accounts.reputation.to_account_info(),
+                client_stats: ctx.accounts.client_stats.to_account_info(),
+                bond: ctx.accounts.bond.as_ref().map(|bond| bond.to_account_info()),
+                bond_vault_token_account: ctx
+                    .accounts
+                    .bond_vault_token_account
+                    .as_ref()
+                    .map(|vault| vault.to_account_info()),
+                config: ctx.accounts.config.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                clock: ctx.accounts.clock.to_account_info(),
+            },
+            &[treasury_seeds],
+        ))
+    }
+}
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"treasury", authority.key().as_ref()], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    // Any token account the treasury owns, not necessarily its ATA
+    #[account(mut, token::mint = mint, token::authority = treasury)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Checked by the gateway
+    pub instructions: UncheckedAccount<'info>,
+    /// CHECK: Created by the gateway
+    #[account(mut)]
+    pub order: UncheckedAccount<'info>,
+    /// CHECK: Created by the gateway
+    #[account(mut)]
+    pub order_vault_token_account: UncheckedAccount<'info>,
+    /// CHECK: Checked by the gateway
+    #[account(mut)]
+    pub client_stats: UncheckedAccount<'info>,
+    /// CHECK: Checked by the gateway
+    pub config: UncheckedAccount<'info>,
+    /// CHECK: Checked by the gateway
+    #[account(mut)]
+    pub bond: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+    pub gateway_program: Program<'info, Gateway>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct RefundOrder<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"treasury", authority.key().as_ref()], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    // Receives the refund, any token account the treasury owns
+    #[account(mut, token::mint = mint, token::authority = treasury)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Checked by the gateway
+    #[account(mut)]
+    pub order: UncheckedAccount<'info>,
+    /// CHECK: Checked by the gateway
+    #[account(mut)]
+    pub order_vault_token_account: UncheckedAccount<'info>,
+    /// CHECK: Checked by the gateway
+    #[account(mut)]
+    pub reputation: UncheckedAccount<'info>,
+    /// CHECK: Checked by the gateway
+    #[account(mut)]
+    pub client_stats: UncheckedAccount<'info>,
+    /// CHECK: Checked by the gateway, required when the order is bonded
+    #[account(mut)]
+    pub bond: Option<UncheckedAccount<'info>>,
+    /// CHECK: Checked by the gateway, required when the order is bonded
+    #[account(mut)]
+    pub bond_vault_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Checked by the gateway
+    pub config: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+    pub gateway_program: Program<'info, Gateway>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
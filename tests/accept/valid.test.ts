@@ -0,0 +1,53 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getAcceptDeliveryTransaction, getDeliverTransaction } from "../helpers/deliver";
+import { getUpdateConfigTransaction } from "../helpers/config";
+
+async function commitAndDeliver(ctx: TestContext, job_name: string): Promise<TestContext> {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, job_name, deadline);
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  return orderCtx;
+}
+
+export async function acceptDeliverySuccess(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { challengeWindow: new anchor.BN(ONE_DAY_IN_SECONDS) }).rpc();
+  try {
+    const orderCtx = await commitAndDeliver(ctx, "acceptDeliverySuccess");
+    const reputationBefore = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+
+    // Accepting releases the held-back funds before the challenge window ends
+    await getAcceptDeliveryTransaction(orderCtx, 5).rpc();
+
+    const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+    assert.isDefined(order.status.completed);
+    assert.equal(order.rating, 5);
+    assert.isAbove(order.acceptedAt.toNumber(), 0);
+
+    const vaultBalance = (await getAccount(ctx.connection, ctx.vaultTokenAccount)).amount;
+    assert.equal(vaultBalance, ctx.price);
+
+    const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    assert.equal(reputation.ordersAccepted.toNumber(), reputationBefore.ordersAccepted.toNumber() + 1);
+    assert.equal(reputation.ratingsSum.toNumber(), reputationBefore.ratingsSum.toNumber() + 5);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+export async function acceptDeliveryInvalidRating(ctx: TestContext) {
+  const orderCtx = await commitAndDeliver(ctx, "acceptDeliveryInvalidRating");
+
+  try {
+    await getAcceptDeliveryTransaction(orderCtx, 6).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "InvalidRating");
+  }
+}
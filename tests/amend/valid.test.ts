@@ -0,0 +1,108 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { bondAccounts } from "../helpers/bond";
+import { signEd25519 } from "../helpers/commit";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { Amendment, serializeAmendment } from "../utils";
+
+export const EXTRA = BigInt(400_000);
+
+export async function amendOrder(ctx: TestContext, amendment: Amendment) {
+  const message = serializeAmendment(amendment);
+  const signature = await signEd25519(message, ctx.service.secretKey);
+  const tx = new anchor.web3.Transaction()
+    .add(
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: ctx.service.publicKey.toBytes(),
+        message,
+        signature,
+      })
+    )
+    .add(
+      await ctx.program.methods
+        .amendOrder()
+        .accounts({
+          user: ctx.user.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          order: ctx.orderPda,
+          userTokenAccount: ctx.userTokenAccount,
+          orderVaultTokenAccount: ctx.orderVaultTokenAccount,
+          bond: bondAccounts(ctx).bondPda,
+          config: ctx.configPda,
+          mint: ctx.mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .instruction()
+    );
+  return sendAndConfirmTransaction(ctx.connection, tx, [ctx.user.payer]);
+}
+
+export function buildAmendment(ctx: TestContext, amendment: number, deadline: bigint | null): Amendment {
+  return new Amendment({
+    order: ctx.orderPda.toBytes(),
+    amendment,
+    price: ctx.price + EXTRA,
+    deadline,
+    price_valid_until: BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS),
+  });
+}
+
+export async function amendOrderSuccess(ctx: TestContext) {
+  const now = Math.floor(Date.now() / 1000);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(
+    ctx,
+    "amendOrderSuccess",
+    BigInt(now + ONE_DAY_IN_SECONDS)
+  );
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+  const newDeadline = BigInt(now + 2 * ONE_DAY_IN_SECONDS);
+
+  await amendOrder(orderCtx, buildAmendment(orderCtx, 0, newDeadline));
+
+  const order = await ctx.program.account.order.fetch(orderPda);
+  assert.equal(order.price.toString(), (ctx.price + EXTRA).toString());
+  assert.equal(order.deadline.toString(), newDeadline.toString());
+  assert.equal(order.amendments, 1);
+
+  const { amount: escrowed } = await getAccount(ctx.connection, orderVaultTokenAccount);
+  assert.equal(escrowed, ctx.price + EXTRA);
+}
+
+export async function amendVestingDeadline(ctx: TestContext) {
+  const now = Math.floor(Date.now() / 1000);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(
+    ctx,
+    "amendVestingDeadline",
+    BigInt(now + ONE_DAY_IN_SECONDS),
+    { vesting: true }
+  );
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+
+  try {
+    await amendOrder(orderCtx, buildAmendment(orderCtx, 0, BigInt(now + 2 * ONE_DAY_IN_SECONDS)));
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.include(error.toString(), "InvalidAmendment");
+  }
+}
+
+export async function amendOrderReplay(ctx: TestContext) {
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(
+    ctx,
+    "amendOrderReplay",
+    BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS)
+  );
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+  const amendment = buildAmendment(orderCtx, 0, null);
+  await amendOrder(orderCtx, amendment);
+
+  try {
+    await amendOrder(orderCtx, amendment);
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.include(error.toString(), "InvalidAmendment");
+  }
+}
@@ -0,0 +1,60 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { commitOrder } from "../helpers/order";
+
+// `ctx.user` acts as the treasury paying for an order placed for someone else
+export async function treasuryPaysAndGetsRefund(ctx: TestContext) {
+  const beneficiary = Keypair.generate().publicKey;
+  const { amount: treasuryBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(
+    { ...ctx, beneficiary },
+    "treasuryPaysAndGetsRefund"
+  );
+
+  const order = await ctx.program.account.order.fetch(orderPda);
+  assert.equal(order.user.toBase58(), beneficiary.toBase58());
+  assert.equal(order.payer.toBase58(), ctx.user.publicKey.toBase58());
+
+  const { amount: treasuryAfterCommit } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(treasuryBefore - treasuryAfterCommit, ctx.price);
+
+  // The order's deadline has passed, so the treasury takes the escrow back
+  await ctx.program.methods
+    .refund()
+    .accounts({
+      user: ctx.user.publicKey,
+      order: orderPda,
+      userTokenAccount: ctx.userTokenAccount,
+      orderVaultTokenAccount,
+      reputation: ctx.reputationPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  const { amount: treasuryAfterRefund } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(treasuryAfterRefund, treasuryBefore);
+}
+
+// An offer made to one client cannot be redeemed for another
+export async function offerBoundToBeneficiary(ctx: TestContext) {
+  const beneficiary = Keypair.generate().publicKey;
+  try {
+    await commitOrder(
+      { ...ctx, beneficiary },
+      "offerBoundToBeneficiary",
+      undefined,
+      { user: ctx.user.publicKey.toBytes() }
+    );
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.include(error.toString(), "InvalidUser");
+  }
+}
@@ -0,0 +1,143 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import Keccak from 'keccak';
+import { TestContext } from "../setup";
+import { bondAccounts } from "../helpers/bond";
+import { ONE_DAY_IN_SECONDS } from "../helpers/order";
+
+export interface BiddingAccounts {
+  bidder: Keypair;
+  jobHash: Buffer;
+  jobRequestPda: PublicKey;
+  bidPda: PublicKey;
+  orderPda: PublicKey;
+  orderVaultTokenAccount: PublicKey;
+}
+
+export async function registerProvider(ctx: TestContext, authority: Keypair) {
+  const sig = await ctx.connection.requestAirdrop(authority.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
+  await ctx.connection.confirmTransaction(sig, "confirmed");
+
+  const [providerPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("provider"), authority.publicKey.toBuffer()],
+    ctx.program.programId
+  );
+  const [reputationPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reputation"), authority.publicKey.toBuffer()],
+    ctx.program.programId
+  );
+
+  await ctx.program.methods
+    .registerProvider(authority.publicKey)
+    .accounts({
+      authority: authority.publicKey,
+      provider: providerPda,
+      reputation: reputationPda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc();
+
+  return { providerPda, reputationPda };
+}
+
+export async function openJobRequest(ctx: TestContext, job_name: string, budget: bigint): Promise<BiddingAccounts> {
+  const jobHash = Keccak('keccak256').update(job_name).digest();
+  const [jobRequestPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("job-request"), ctx.user.publicKey.toBuffer(), jobHash],
+    ctx.program.programId
+  );
+  const now = Math.floor(Date.now() / 1000);
+
+  await ctx.program.methods
+    .openJobRequest(Array.from(jobHash), new anchor.BN(budget.toString()), new anchor.BN(now + ONE_DAY_IN_SECONDS))
+    .accounts({
+      user: ctx.user.publicKey,
+      jobRequest: jobRequestPda,
+      mint: ctx.mint,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  const bidder = Keypair.generate();
+  const [bidPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("bid"), jobRequestPda.toBuffer(), bidder.publicKey.toBuffer()],
+    ctx.program.programId
+  );
+  const [orderPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("order"), ctx.user.publicKey.toBuffer(), jobHash],
+    ctx.program.programId
+  );
+  const [orderVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), orderPda.toBuffer()],
+    ctx.program.programId
+  );
+
+  return { bidder, jobHash, jobRequestPda, bidPda, orderPda, orderVaultTokenAccount };
+}
+
+function placeBid(ctx: TestContext, accounts: BiddingAccounts, price: bigint) {
+  const now = Math.floor(Date.now() / 1000);
+  return ctx.program.methods
+    .placeBid(new anchor.BN(price.toString()), new anchor.BN(now + 2 * ONE_DAY_IN_SECONDS))
+    .accounts({
+      authority: accounts.bidder.publicKey,
+      jobRequest: accounts.jobRequestPda,
+      bid: accounts.bidPda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([accounts.bidder]);
+}
+
+export async function bidSuccess(ctx: TestContext) {
+  const accounts = await openJobRequest(ctx, "bidSuccess", ctx.price);
+  await registerProvider(ctx, accounts.bidder);
+  await placeBid(ctx, accounts, ctx.price).rpc();
+
+  const jobRequest = await ctx.program.account.jobRequest.fetch(accounts.jobRequestPda);
+  assert.equal(jobRequest.bidCount, 1);
+
+  await ctx.program.methods
+    .acceptBid()
+    .accounts({
+      user: ctx.user.publicKey,
+      jobRequest: accounts.jobRequestPda,
+      bid: accounts.bidPda,
+      order: accounts.orderPda,
+      userTokenAccount: ctx.userTokenAccount,
+      orderVaultTokenAccount: accounts.orderVaultTokenAccount,
+      bond: bondAccounts(ctx, accounts.bidder.publicKey).bondPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  const order = await ctx.program.account.order.fetch(accounts.orderPda);
+  assert.isDefined(order.status.started);
+  assert.equal(order.provider.toBase58(), accounts.bidder.publicKey.toBase58());
+
+  const vaultAccount = await getAccount(ctx.connection, accounts.orderVaultTokenAccount);
+  assert.equal(vaultAccount.amount, ctx.price);
+
+  const awarded = await ctx.program.account.jobRequest.fetch(accounts.jobRequestPda);
+  assert.isDefined(awarded.status.awarded);
+}
+
+export async function bidOverBudget(ctx: TestContext) {
+  const accounts = await openJobRequest(ctx, "bidOverBudget", ctx.price);
+  await registerProvider(ctx, accounts.bidder);
+
+  try {
+    await placeBid(ctx, accounts, ctx.price + BigInt(1)).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "BudgetExceeded");
+  }
+}
@@ -0,0 +1,170 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { TestContext } from "../setup";
+import { getUpdateConfigTransaction } from "../helpers/config";
+import { bondAccounts } from "../helpers/bond";
+import { getAcceptDeliveryTransaction, getDeliverTransaction } from "../helpers/deliver";
+import { commitOrder } from "../helpers/order";
+import { estimate, evaluate, rfqAccounts } from "../rfq/valid.test";
+import { amendOrder, buildAmendment, EXTRA } from "../amend/valid.test";
+
+const BOND = BigInt(1_000_000);
+const SLASH_BPS = 1_000;
+
+export async function depositBond(ctx: TestContext, amount: bigint) {
+  const { bondPda, bondVaultTokenAccount } = bondAccounts(ctx);
+  await mintTo(ctx.connection, ctx.user.payer, ctx.mint, ctx.recipientTokenAccount, ctx.user.payer, amount);
+  await ctx.program.methods
+    .depositBond(new anchor.BN(amount.toString()))
+    .accounts({
+      authority: ctx.service.publicKey,
+      bond: bondPda,
+      bondVaultTokenAccount,
+      authorityTokenAccount: ctx.recipientTokenAccount,
+      reputation: ctx.reputationPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.service])
+    .rpc();
+}
+
+export async function bondSlashedOnRefund(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { bondSlashBps: SLASH_BPS, bondLeverage: 2 }).rpc();
+  try {
+    await depositBond(ctx, BOND);
+    const orderCtx = await commitOrder(ctx, "bondSlashedOnRefund");
+    const { bondPda, bondVaultTokenAccount } = bondAccounts(ctx);
+
+    let bond = await ctx.program.account.bond.fetch(bondPda);
+    assert.equal(bond.outstanding.toString(), ctx.price.toString());
+
+    const { amount: userBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+    await ctx.program.methods
+      .refund()
+      .accounts({
+        user: ctx.user.publicKey,
+        order: orderCtx.orderPda,
+        userTokenAccount: ctx.userTokenAccount,
+        orderVaultTokenAccount: orderCtx.orderVaultTokenAccount,
+        reputation: ctx.reputationPda,
+        bond: bondPda,
+        bondVaultTokenAccount,
+        mint: ctx.mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([ctx.user.payer])
+      .rpc();
+
+    // Escrow back plus the slashed share of the bond
+    const slashed = BOND * BigInt(SLASH_BPS) / BigInt(10_000);
+    const { amount: userAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+    assert.equal(userAfter - userBefore, ctx.price + slashed);
+
+    bond = await ctx.program.account.bond.fetch(bondPda);
+    assert.equal(bond.outstanding.toNumber(), 0);
+    assert.equal(bond.amount.toString(), (BOND - slashed).toString());
+    const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    assert.equal(reputation.bond.toString(), (BOND - slashed).toString());
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+export async function bondCapacityExceeded(ctx: TestContext) {
+  // Leverage 0 backs work up to the bond's own size, below one order's price after the slash
+  await getUpdateConfigTransaction(ctx, { bondSlashBps: SLASH_BPS }).rpc();
+  try {
+    await commitOrder(ctx, "bondCapacityOne");
+    await commitOrder(ctx, "bondCapacityTwo");
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.include(error.toString(), "BondCapacityExceeded");
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+// Orders accepted from an evaluation are backed by the bond like committed ones
+export async function bondLockedOnEvaluationCommit(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { bondSlashBps: SLASH_BPS, bondLeverage: 2 }).rpc();
+  try {
+    const { bondPda } = bondAccounts(ctx);
+    const before = await ctx.program.account.bond.fetch(bondPda);
+    const { jobHash, orderPda, orderVaultTokenAccount } = rfqAccounts(ctx, "bondEvaluation");
+    await estimate(ctx, jobHash, orderPda, ctx.price);
+    await evaluate(ctx, orderPda, ctx.price).rpc();
+    await ctx.program.methods
+      .commitEvaluation()
+      .accounts({
+        user: ctx.user.publicKey,
+        order: orderPda,
+        userTokenAccount: ctx.userTokenAccount,
+        orderVaultTokenAccount,
+        bond: bondPda,
+        config: ctx.configPda,
+        mint: ctx.mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.user.payer])
+      .rpc();
+
+    const order = await ctx.program.account.order.fetch(orderPda);
+    assert.isTrue(order.bonded);
+    const after = await ctx.program.account.bond.fetch(bondPda);
+    assert.equal(after.outstanding.sub(before.outstanding).toString(), ctx.price.toString());
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+// Delivery alone does not free the bond, the order must be final
+export async function bondHeldUntilAccepted(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { bondSlashBps: SLASH_BPS, bondLeverage: 3 }).rpc();
+  try {
+    const { bondPda } = bondAccounts(ctx);
+    const before = await ctx.program.account.bond.fetch(bondPda);
+    const deadline = BigInt(Math.floor(Date.now() / 1000) + 60 * 60 * 24);
+    const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "bondHeldUntilAccepted", deadline);
+    const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+
+    await getDeliverTransaction(orderCtx, ctx.service).rpc();
+    let bond = await ctx.program.account.bond.fetch(bondPda);
+    assert.equal(bond.outstanding.sub(before.outstanding).toString(), ctx.price.toString());
+
+    await getAcceptDeliveryTransaction(orderCtx, 5).rpc();
+    bond = await ctx.program.account.bond.fetch(bondPda);
+    assert.equal(bond.outstanding.toString(), before.outstanding.toString());
+    const order = await ctx.program.account.order.fetch(orderPda);
+    assert.equal(order.bondLocked.toNumber(), 0);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+// Raising the price through an amendment locks the increase too
+export async function bondRelockedOnAmendment(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { bondSlashBps: SLASH_BPS, bondLeverage: 3 }).rpc();
+  try {
+    await mintTo(ctx.connection, ctx.user.payer, ctx.mint, ctx.userTokenAccount, ctx.user.payer, ctx.price + EXTRA);
+    const { bondPda } = bondAccounts(ctx);
+    const before = await ctx.program.account.bond.fetch(bondPda);
+    const deadline = BigInt(Math.floor(Date.now() / 1000) + 60 * 60 * 24);
+    const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "bondRelockedOnAmendment", deadline);
+    const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+
+    await amendOrder(orderCtx, buildAmendment(orderCtx, 0, null));
+
+    const bond = await ctx.program.account.bond.fetch(bondPda);
+    assert.equal(bond.outstanding.sub(before.outstanding).toString(), (ctx.price + EXTRA).toString());
+    const order = await ctx.program.account.order.fetch(orderPda);
+    assert.equal(order.bondLocked.toString(), (ctx.price + EXTRA).toString());
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
@@ -0,0 +1,66 @@
+import { assert } from "chai";
+import { getAccount } from "@solana/spl-token";
+import { TestContext } from "../setup";
+import { commitOrder } from "../helpers/order";
+import { getDeliverTransaction, getFinalizeTransaction } from "../helpers/deliver";
+
+const ONE_HOUR_IN_SECONDS = 60 * 60;
+const BONUS = BigInt(50_000);
+
+async function commitBonusOrder(ctx: TestContext, job_name: string, bonus_deadline: bigint): Promise<TestContext> {
+  const now = Math.floor(Date.now() / 1000);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(
+    ctx,
+    job_name,
+    BigInt(now + 2 * ONE_HOUR_IN_SECONDS),
+    { bonus: BONUS, bonus_deadline }
+  );
+  return { ...ctx, orderPda, orderVaultTokenAccount };
+}
+
+async function vaultBalance(ctx: TestContext): Promise<bigint> {
+  return getAccount(ctx.connection, ctx.vaultTokenAccount)
+    .then((account) => account.amount)
+    .catch(() => BigInt(0));
+}
+
+export async function bonusEarned(ctx: TestContext) {
+  const vaultBefore = await vaultBalance(ctx);
+  const orderCtx = await commitBonusOrder(
+    ctx,
+    "bonusEarned",
+    BigInt(Math.floor(Date.now() / 1000) + ONE_HOUR_IN_SECONDS)
+  );
+
+  // Price and bonus are escrowed together
+  const { amount: escrowed } = await getAccount(ctx.connection, orderCtx.orderVaultTokenAccount);
+  assert.equal(escrowed, ctx.price + BONUS);
+
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.isTrue(order.bonusEarned);
+
+  await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+  assert.equal(await vaultBalance(ctx) - vaultBefore, ctx.price + BONUS);
+}
+
+export async function bonusForfeited(ctx: TestContext) {
+  const vaultBefore = await vaultBalance(ctx);
+  const { amount: userBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  const orderCtx = await commitBonusOrder(
+    ctx,
+    "bonusForfeited",
+    BigInt(Math.floor(Date.now() / 1000) - ONE_HOUR_IN_SECONDS)
+  );
+
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.isFalse(order.bonusEarned);
+
+  // The bonus went back to the client on delivery
+  const { amount: userAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(userAfter, userBefore - ctx.price);
+
+  await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+  assert.equal(await vaultBalance(ctx) - vaultBefore, ctx.price);
+}
@@ -0,0 +1,138 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey, sendAndConfirmTransaction } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { signEd25519 } from "../helpers/commit";
+import { serializeVoucher, Voucher } from "../utils";
+
+function channelAccounts(ctx: TestContext, channelId: number) {
+  const [channelPda] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("channel"),
+      ctx.user.publicKey.toBuffer(),
+      ctx.service.publicKey.toBuffer(),
+      new anchor.BN(channelId).toArrayLike(Buffer, "le", 8),
+    ],
+    ctx.program.programId
+  );
+  const [channelVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("channel-vault"), channelPda.toBuffer()],
+    ctx.program.programId
+  );
+  return { channelPda, channelVaultTokenAccount };
+}
+
+async function ordersCompleted(ctx: TestContext): Promise<number> {
+  const reputation = await ctx.program.account.reputation.fetchNullable(ctx.reputationPda);
+  return reputation ? reputation.ordersCompleted.toNumber() : 0;
+}
+
+async function openChannel(ctx: TestContext, channelId: number, timeout: number) {
+  const { channelPda, channelVaultTokenAccount } = channelAccounts(ctx, channelId);
+  await ctx.program.methods
+    .openChannel(new anchor.BN(channelId), new anchor.BN(ctx.price.toString()), new anchor.BN(timeout))
+    .accounts({
+      client: ctx.user.publicKey,
+      provider: ctx.service.publicKey,
+      channel: channelPda,
+      channelVaultTokenAccount,
+      clientTokenAccount: ctx.userTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+  return { channelPda, channelVaultTokenAccount };
+}
+
+async function closeChannel(ctx: TestContext, channelPda: PublicKey, channelVaultTokenAccount: PublicKey, amount: bigint) {
+  const message = serializeVoucher(new Voucher({ channel: channelPda.toBytes(), amount }));
+  const signature = await signEd25519(message, ctx.user.payer.secretKey);
+  const tx = new anchor.web3.Transaction()
+    .add(
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: ctx.user.publicKey.toBytes(),
+        message,
+        signature,
+      })
+    )
+    .add(
+      await ctx.program.methods
+        .closeChannel()
+        .accounts({
+          authority: ctx.service.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          client: ctx.user.publicKey,
+          channel: channelPda,
+          channelVaultTokenAccount,
+          clientTokenAccount: ctx.userTokenAccount,
+          vaultAuthority: ctx.vaultAuthority,
+          vaultTokenAccount: ctx.vaultTokenAccount,
+          reputation: ctx.reputationPda,
+          config: ctx.configPda,
+          mint: ctx.mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction()
+    );
+  await sendAndConfirmTransaction(ctx.connection, tx, [ctx.service]);
+}
+
+export async function closeChannelSuccess(ctx: TestContext) {
+  const { amount: amountBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  const { channelPda, channelVaultTokenAccount } = await openChannel(ctx, 1, 60 * 60);
+  const completedBefore = await ordersCompleted(ctx);
+
+  // Only the latest voucher matters, earlier ones are simply never submitted
+  const amount = BigInt(400_000);
+  await closeChannel(ctx, channelPda, channelVaultTokenAccount, amount);
+
+  const channel = await ctx.program.account.channel.fetch(channelPda);
+  assert.isDefined(channel.status.closed);
+  assert.equal(channel.settled.toString(), amount.toString());
+
+  const vaultBalance = (await getAccount(ctx.connection, ctx.vaultTokenAccount)).amount;
+  assert.equal(vaultBalance, amount);
+  const { amount: amountAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(amountBefore - amountAfter, amount);
+  assert.equal(await ordersCompleted(ctx), completedBefore + 1);
+}
+
+// A zero-amount voucher costs nothing but rent, so it must not count as work
+export async function closeChannelWithoutPayment(ctx: TestContext) {
+  const { channelPda, channelVaultTokenAccount } = await openChannel(ctx, 3, 60 * 60);
+  const completedBefore = await ordersCompleted(ctx);
+
+  await closeChannel(ctx, channelPda, channelVaultTokenAccount, BigInt(0));
+
+  const channel = await ctx.program.account.channel.fetch(channelPda);
+  assert.isDefined(channel.status.closed);
+  assert.equal(await ordersCompleted(ctx), completedBefore);
+}
+
+export async function forceCloseBeforeTimeout(ctx: TestContext) {
+  const { channelPda, channelVaultTokenAccount } = await openChannel(ctx, 2, 60 * 60);
+
+  try {
+    await ctx.program.methods
+      .forceCloseChannel()
+      .accounts({
+        client: ctx.user.publicKey,
+        channel: channelPda,
+        channelVaultTokenAccount,
+        clientTokenAccount: ctx.userTokenAccount,
+        mint: ctx.mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.user.payer])
+      .rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "ChannelNotExpired");
+  }
+}
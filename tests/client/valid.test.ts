@@ -0,0 +1,59 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { bondAccounts } from "../helpers/bond";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getDeliverTransaction } from "../helpers/deliver";
+
+function clientStatsPda(ctx: TestContext, client: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("client-stats"), client.toBuffer()],
+    ctx.program.programId
+  );
+  return pda;
+}
+
+// A fresh beneficiary keeps the stats independent of the rest of the suite
+export async function unsatisfiedClientRefused(ctx: TestContext) {
+  const client = Keypair.generate();
+  const sig = await ctx.connection.requestAirdrop(client.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
+  await ctx.connection.confirmTransaction(sig, "confirmed");
+  const clientCtx = { ...ctx, beneficiary: client.publicKey };
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(clientCtx, "unsatisfiedClientOne", deadline);
+  await getDeliverTransaction({ ...clientCtx, orderPda, orderVaultTokenAccount }, ctx.service).rpc();
+  await ctx.program.methods
+    .acceptDelivery(1)
+    .accounts({
+      user: client.publicKey,
+      payer: ctx.user.publicKey,
+      payerTokenAccount: ctx.userTokenAccount,
+      order: orderPda,
+      orderVaultTokenAccount,
+      vaultAuthority: ctx.vaultAuthority,
+      vaultTokenAccount: ctx.vaultTokenAccount,
+      reputation: ctx.reputationPda,
+      clientStats: clientStatsPda(ctx, client.publicKey),
+      bond: bondAccounts(ctx).bondPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([client])
+    .rpc();
+
+  const stats = await ctx.program.account.clientStats.fetch(clientStatsPda(ctx, client.publicKey));
+  assert.equal(stats.ordersPlaced.toNumber(), 1);
+  assert.equal(stats.ordersCompleted.toNumber(), 1);
+  assert.equal(stats.unsatisfied.toNumber(), 1);
+
+  try {
+    await commitOrder(clientCtx, "unsatisfiedClientTwo", deadline, { max_client_unsatisfied_bps: 5_000 });
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.include(error.toString(), "ClientReputationTooLow");
+  }
+}
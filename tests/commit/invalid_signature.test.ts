@@ -66,6 +66,7 @@ export async function commitMissingInstruction(testCtx: TestContext) {
       .commit(Array.from(testCtx.jobHash))
       .accounts({
         user: testCtx.user.publicKey,
+        beneficiary: testCtx.user.publicKey,
         order: testCtx.orderPda,
         userTokenAccount: testCtx.userTokenAccount,
         orderVaultTokenAccount: testCtx.orderVaultTokenAccount,
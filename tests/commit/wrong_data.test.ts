@@ -35,6 +35,7 @@ export async function commitWrongMint(testCtx: TestContext) {
       .commit(Array.from(testCtx.jobHash))
       .accounts({
         user: testCtx.user.publicKey,
+        beneficiary: testCtx.user.publicKey,
         userTokenAccount: fakeTokenAccount.address,
         orderVaultTokenAccount: testCtx.orderVaultTokenAccount,
         mint: fakeMint,
@@ -106,6 +107,7 @@ export async function commitWrongTokenAccountOwner(testCtx: TestContext) {
       .commit(Array.from(testCtx.jobHash))
       .accounts({
         user: testCtx.user.publicKey,
+        beneficiary: testCtx.user.publicKey,
         userTokenAccount: fakeTokenAccount.address,
         orderVaultTokenAccount: testCtx.orderVaultTokenAccount,
         mint: testCtx.mint,
@@ -126,4 +128,19 @@ export async function commitWrongTokenAccountOwner(testCtx: TestContext) {
       error.logs.some((log: string) => log.includes("A token owner constraint was violated"))
     );
   }
-} 
\ No newline at end of file
+} 
+export async function commitUnsupportedVersion(testCtx: TestContext) {
+  const message = await buildOrderMessage(testCtx, { version: 0 });
+  const signature = await signEd25519(message, testCtx.service.secretKey);
+  const tx = await buildCommitTransaction(testCtx, message, signature);
+
+  try {
+    await prepareAndSubmitTransaction(testCtx, tx);
+    assert.fail("Should have failed with UnsupportedOfferVersion");
+  } catch (error: any) {
+    assert.isDefined(error.logs);
+    assert.isTrue(
+      error.logs.some((log: string) => log.includes("Error Code: UnsupportedOfferVersion"))
+    );
+  }
+}
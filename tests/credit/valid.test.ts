@@ -0,0 +1,134 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey, sendAndConfirmTransaction } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { signEd25519 } from "../helpers/commit";
+import { serializeUsageReceipt, UsageReceipt } from "../utils";
+
+function creditAccounts(ctx: TestContext) {
+  const [creditPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("credit"), ctx.user.publicKey.toBuffer(), ctx.service.publicKey.toBuffer(), ctx.mint.toBuffer()],
+    ctx.program.programId
+  );
+  const [creditVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("credit-vault"), creditPda.toBuffer()],
+    ctx.program.programId
+  );
+  return { creditPda, creditVaultTokenAccount };
+}
+
+async function topUp(ctx: TestContext, amount: bigint) {
+  const { creditPda, creditVaultTokenAccount } = creditAccounts(ctx);
+  await ctx.program.methods
+    .topUpCredit(new anchor.BN(amount.toString()))
+    .accounts({
+      client: ctx.user.publicKey,
+      provider: ctx.service.publicKey,
+      credit: creditPda,
+      creditVaultTokenAccount,
+      clientTokenAccount: ctx.userTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+}
+
+async function draw(ctx: TestContext, cumulativeAmount: bigint, counter: bigint) {
+  const { creditPda, creditVaultTokenAccount } = creditAccounts(ctx);
+  const message = serializeUsageReceipt(new UsageReceipt({
+    credit: creditPda.toBytes(),
+    cumulative_amount: cumulativeAmount,
+    counter,
+  }));
+  const signature = await signEd25519(message, ctx.user.payer.secretKey);
+
+  const tx = new anchor.web3.Transaction()
+    .add(
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: ctx.user.publicKey.toBytes(),
+        message,
+        signature,
+      })
+    )
+    .add(
+      await ctx.program.methods
+        .drawCredit()
+        .accounts({
+          authority: ctx.service.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          credit: creditPda,
+          creditVaultTokenAccount,
+          vaultAuthority: ctx.vaultAuthority,
+          vaultTokenAccount: ctx.vaultTokenAccount,
+          mint: ctx.mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction()
+    );
+
+  return sendAndConfirmTransaction(ctx.connection, tx, [ctx.service]);
+}
+
+export async function drawCreditSuccess(ctx: TestContext) {
+  await topUp(ctx, ctx.price);
+  await draw(ctx, BigInt(100), BigInt(1));
+  await draw(ctx, BigInt(250), BigInt(2));
+
+  const { creditPda, creditVaultTokenAccount } = creditAccounts(ctx);
+  const credit = await ctx.program.account.creditAccount.fetch(creditPda);
+  assert.equal(credit.drawn.toNumber(), 250);
+  assert.equal(credit.counter.toNumber(), 2);
+
+  const vaultBalance = (await getAccount(ctx.connection, ctx.vaultTokenAccount)).amount;
+  assert.equal(vaultBalance, BigInt(250));
+  const creditBalance = (await getAccount(ctx.connection, creditVaultTokenAccount)).amount;
+  assert.equal(creditBalance, ctx.price - BigInt(250));
+}
+
+export async function drawStaleReceipt(ctx: TestContext) {
+  try {
+    await draw(ctx, BigInt(300), BigInt(2));
+    assert.fail("Should have failed");
+  } catch (error: any) {
+    assert.isDefined(error.logs);
+    assert.isTrue(
+      error.logs.some((log: string) => log.includes("Error Code: StaleReceipt"))
+    );
+  }
+}
+
+export async function withdrawCreditSuccess(ctx: TestContext) {
+  const { creditPda, creditVaultTokenAccount } = creditAccounts(ctx);
+  const { amount: amountBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+
+  await ctx.program.methods
+    .requestCreditWithdrawal()
+    .accounts({
+      client: ctx.user.publicKey,
+      credit: creditPda,
+      config: ctx.configPda,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  // The default notice period is zero, so the balance is available right away
+  await ctx.program.methods
+    .withdrawCredit()
+    .accounts({
+      client: ctx.user.publicKey,
+      credit: creditPda,
+      creditVaultTokenAccount,
+      clientTokenAccount: ctx.userTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  const { amount: amountAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(amountAfter - amountBefore, ctx.price - BigInt(250));
+}
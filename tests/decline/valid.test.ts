@@ -3,7 +3,7 @@ import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { TestContext } from "../setup";
 import * as anchor from "@coral-xyz/anchor";
 
-export async function declineSuccess(ctx: TestContext) {
+export async function declineSuccess(ctx: TestContext, expectedBalance: bigint = BigInt(2_000_000)) {
   // Call the decline instruction
   await getDeclineTransaction(ctx, ctx.service).rpc();
 
@@ -13,7 +13,7 @@ export async function declineSuccess(ctx: TestContext) {
 
   // Check that the user's token balance is restored
   const userAccount = await getAccount(ctx.connection, ctx.userTokenAccount);
-  assert.equal(Number(userAccount.amount), 2_000_000);
+  assert.equal(userAccount.amount, expectedBalance);
 
   // Check order vault token account is closed
   try {
@@ -34,7 +34,7 @@ function getDeclineTransaction(ctx: TestContext, authority: anchor.web3.Keypair)
       order: ctx.orderPda,
       userTokenAccount: ctx.userTokenAccount,
       orderVaultTokenAccount: ctx.orderVaultTokenAccount,
-      config: ctx.configPda,
+      reputation: ctx.reputationPda,
       mint: ctx.mint,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId
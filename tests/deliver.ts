@@ -2,7 +2,7 @@ import { getAccount } from "@solana/spl-token";
 import { assert } from "chai";
 import { TestContext } from "./setup";
 import { AnchorError } from "@coral-xyz/anchor";
-import { getDeliverTransaction } from "./helpers/deliver";
+import { getDeliverTransaction, getFinalizeTransaction } from "./helpers/deliver";
 import { createMintAndTokenAccount } from "./helpers/commit";
 import { Keypair, sendAndConfirmTransaction, SystemProgram, Transaction } from "@solana/web3.js";
 import { commitOrder } from "./helpers/order";
@@ -10,6 +10,17 @@ import { commitOrder } from "./helpers/order";
 export async function deliverSuccess(ctx: TestContext) {
     await getDeliverTransaction(ctx, ctx.service).rpc();
 
+    const order = await ctx.program.account.order.fetch(ctx.orderPda);
+    assert.isDefined(order.status.delivered);
+
+    // Funds are held back in the order vault until the order is finalized
+    const orderVaultBalance = (await getAccount(ctx.connection, ctx.orderVaultTokenAccount)).amount;
+    assert.equal(orderVaultBalance, ctx.price);
+} 
+
+export async function finalizeSuccess(ctx: TestContext) {
+    await getFinalizeTransaction(ctx, ctx.service).rpc();
+
     const order = await ctx.program.account.order.fetch(ctx.orderPda);
     assert.isDefined(order.status.completed);
     const vaultBalance = (await getAccount(ctx.connection, ctx.vaultTokenAccount)).amount;
@@ -30,7 +41,7 @@ export async function deliverUnauthorized(ctx: TestContext) {
     } catch (error) {
         assert.isTrue(error instanceof AnchorError);
         const err: AnchorError = error;
-        assert.strictEqual(err.error.errorCode.code, "ConstraintHasOne");
+        assert.strictEqual(err.error.errorCode.code, "InvalidProvider");
     }
 }
 
@@ -41,8 +52,7 @@ export async function replayDeliver(ctx: TestContext) {
     } catch (error) {
         assert.isTrue(error instanceof AnchorError);
         const err: AnchorError = error;
-        assert.equal(err.error.origin, "order_vault_token_account");
-        assert.strictEqual(err.error.errorCode.code, "AccountNotInitialized");
+        assert.strictEqual(err.error.errorCode.code, "InvalidOrderStatus");
     }
 }
 
@@ -0,0 +1,55 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getAcceptDeliveryTransaction, getDeliverTransaction, getFinalizeTransaction } from "../helpers/deliver";
+import { getUpdateConfigTransaction } from "../helpers/config";
+import { getDisputeTransaction } from "../dispute/valid.test";
+
+const DEPOSIT = BigInt(20_000);
+
+async function commitWithDeposit(ctx: TestContext, job_name: string): Promise<TestContext> {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, job_name, deadline, { client_deposit: DEPOSIT });
+  return { ...ctx, orderPda, orderVaultTokenAccount };
+}
+
+async function vaultBalance(ctx: TestContext): Promise<bigint> {
+  return getAccount(ctx.connection, ctx.vaultTokenAccount)
+    .then((account) => account.amount)
+    .catch(() => BigInt(0));
+}
+
+export async function depositReturnedOnFinalize(ctx: TestContext) {
+  const { amount: userBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  const orderCtx = await commitWithDeposit(ctx, "depositReturnedOnFinalize");
+
+  const { amount: escrowed } = await getAccount(ctx.connection, orderCtx.orderVaultTokenAccount);
+  assert.equal(escrowed, ctx.price + DEPOSIT);
+
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+
+  const { amount: userAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(userBefore - userAfter, ctx.price);
+}
+
+export async function depositForfeitedAfterDispute(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { challengeWindow: new anchor.BN(ONE_DAY_IN_SECONDS) }).rpc();
+  try {
+    const vaultBefore = await vaultBalance(ctx);
+    const orderCtx = await commitWithDeposit(ctx, "depositForfeitedAfterDispute");
+    await getDeliverTransaction(orderCtx, ctx.service).rpc();
+    await getDisputeTransaction(orderCtx).rpc();
+
+    // Conceding the dispute hands the deposit to the provider
+    await getAcceptDeliveryTransaction(orderCtx, null).rpc();
+
+    const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+    assert.isTrue(order.depositForfeited);
+    assert.equal(await vaultBalance(ctx) - vaultBefore, ctx.price + DEPOSIT);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
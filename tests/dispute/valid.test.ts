@@ -0,0 +1,146 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getDeliverTransaction, getFinalizeTransaction } from "../helpers/deliver";
+import { getUpdateConfigTransaction } from "../helpers/config";
+import { declineSuccess } from "../decline/valid.test";
+
+async function commitAndDeliver(ctx: TestContext, job_name: string): Promise<TestContext> {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, job_name, deadline);
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  return orderCtx;
+}
+
+export function getDisputeTransaction(ctx: TestContext) {
+  return ctx.program.methods
+    .dispute()
+    .accounts({
+      user: ctx.user.publicKey,
+      order: ctx.orderPda,
+      reputation: ctx.reputationPda,
+      config: ctx.configPda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer]);
+}
+
+export async function finalizeDuringChallengeWindow(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { challengeWindow: new anchor.BN(ONE_DAY_IN_SECONDS) }).rpc();
+  const orderCtx = await commitAndDeliver(ctx, "finalizeDuringChallengeWindow");
+
+  try {
+    await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "ChallengeWindowOpen");
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+export async function disputeResolvedByDecline(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, { challengeWindow: new anchor.BN(ONE_DAY_IN_SECONDS) }).rpc();
+  try {
+    const { amount: amountBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+    const orderCtx = await commitAndDeliver(ctx, "disputeResolvedByDecline");
+    await getDisputeTransaction(orderCtx).rpc();
+
+    const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+    assert.isDefined(order.status.disputed);
+
+    // Only the client may release a disputed order
+    try {
+      await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      assert.strictEqual(err.error.errorCode.code, "InvalidOrderStatus");
+    }
+
+    await declineSuccess(orderCtx, amountBefore);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+function getRefundTransaction(ctx: TestContext) {
+  return ctx.program.methods
+    .refund()
+    .accounts({
+      user: ctx.user.publicKey,
+      order: ctx.orderPda,
+      userTokenAccount: ctx.userTokenAccount,
+      orderVaultTokenAccount: ctx.orderVaultTokenAccount,
+      reputation: ctx.reputationPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    })
+    .signers([ctx.user.payer]);
+}
+
+// A dispute the provider never answers ends in a refund
+export async function disputeRefundedAfterTimeout(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, {
+    challengeWindow: new anchor.BN(ONE_DAY_IN_SECONDS),
+    disputePeriod: new anchor.BN(2),
+  }).rpc();
+  try {
+    const { amount: amountBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+    const orderCtx = await commitAndDeliver(ctx, "disputeRefundedAfterTimeout");
+    await getDisputeTransaction(orderCtx).rpc();
+
+    try {
+      await getRefundTransaction(orderCtx).rpc();
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      assert.strictEqual(err.error.errorCode.code, "RefundBeforeDeadline");
+    }
+
+    const [clientStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("client-stats"), ctx.user.publicKey.toBuffer()],
+      ctx.program.programId
+    );
+    const statsBefore = await ctx.program.account.clientStats.fetchNullable(clientStatsPda);
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await getRefundTransaction(orderCtx).rpc();
+
+    // Winning by timeout counts like winning the dispute outright
+    const stats = await ctx.program.account.clientStats.fetch(clientStatsPda);
+    const wonBefore = statsBefore ? statsBefore.disputesWon.toNumber() : 0;
+    assert.equal(stats.disputesWon.toNumber(), wonBefore + 1);
+
+    const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+    assert.isDefined(order.status.refunded);
+    const { amount: amountAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+    assert.equal(amountAfter, amountBefore);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+export async function challengeWindowNeedsDisputePeriod(ctx: TestContext) {
+  try {
+    await getUpdateConfigTransaction(ctx, {
+      challengeWindow: new anchor.BN(ONE_DAY_IN_SECONDS),
+      disputePeriod: new anchor.BN(0),
+    }).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "DisputePeriodRequired");
+  }
+}
@@ -0,0 +1,86 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { createHash, randomBytes } from "crypto";
+import Keccak from 'keccak';
+import { TestContext } from "../setup";
+import { bondAccounts } from "../helpers/bond";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+
+async function deliverEncrypted(ctx: TestContext, job_name: string, key: Buffer): Promise<TestContext> {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, job_name, deadline);
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+
+  const ciphertextHash = Keccak('keccak256').update("encrypted result").digest();
+  const keyHash = createHash("sha256").update(key).digest();
+  await ctx.program.methods
+    .deliverEncrypted(Array.from(ciphertextHash), Array.from(keyHash))
+    .accounts({
+      authority: ctx.service.publicKey,
+      order: orderPda,
+    })
+    .signers([ctx.service])
+    .rpc();
+
+  await ctx.program.methods
+    .acknowledgeCiphertext()
+    .accounts({
+      user: ctx.user.publicKey,
+      order: orderPda,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  return orderCtx;
+}
+
+function getRevealKeyTransaction(ctx: TestContext, key: Buffer) {
+  return ctx.program.methods
+    .revealKey(Array.from(key))
+    .accounts({
+      authority: ctx.service.publicKey,
+      user: ctx.user.publicKey,
+      order: ctx.orderPda,
+      orderVaultTokenAccount: ctx.orderVaultTokenAccount,
+      vaultAuthority: ctx.vaultAuthority,
+      userTokenAccount: ctx.userTokenAccount,
+      vaultTokenAccount: ctx.vaultTokenAccount,
+      reputation: ctx.reputationPda,
+      bond: bondAccounts(ctx).bondPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.service]);
+}
+
+export async function revealKeySuccess(ctx: TestContext) {
+  const key = randomBytes(32);
+  const orderCtx = await deliverEncrypted(ctx, "revealKeySuccess", key);
+
+  let order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.isDefined(order.status.acknowledged);
+
+  await getRevealKeyTransaction(orderCtx, key).rpc();
+
+  order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.isDefined(order.status.completed);
+  assert.equal(Buffer.from(order.decryptionKey).toString("hex"), key.toString("hex"));
+
+  const vaultBalance = (await getAccount(ctx.connection, ctx.vaultTokenAccount)).amount;
+  assert.equal(vaultBalance, ctx.price);
+}
+
+export async function revealWrongKey(ctx: TestContext) {
+  const orderCtx = await deliverEncrypted(ctx, "revealWrongKey", randomBytes(32));
+
+  try {
+    await getRevealKeyTransaction(orderCtx, randomBytes(32)).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "KeyHashMismatch");
+  }
+}
@@ -0,0 +1,109 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getAcceptDeliveryTransaction, getDeliverTransaction } from "../helpers/deliver";
+import { bondAccounts } from "../helpers/bond";
+
+const SCORE_UNIT = 1_000_000;
+
+function mintConfigPda(ctx: TestContext): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint-config"), ctx.mint.toBuffer()],
+    ctx.program.programId
+  );
+  return pda;
+}
+
+function setMintConfig(ctx: TestContext, unit: number, minFeedbackPrice: number) {
+  return ctx.program.methods
+    .setMintConfig(new anchor.BN(unit), new anchor.BN(minFeedbackPrice))
+    .accounts({
+      authority: ctx.service.publicKey,
+      config: ctx.configPda,
+      mintConfig: mintConfigPda(ctx),
+      mint: ctx.mint,
+    })
+    .signers([ctx.service])
+    .rpc();
+}
+
+async function acceptRated(ctx: TestContext, jobName: string) {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, jobName, deadline);
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  await getAcceptDeliveryTransaction(orderCtx, 5, { mintConfig: mintConfigPda(ctx) }).rpc();
+}
+
+// A rating counts in the weighted view only when the order is priced at
+// or above the mint's minimum, and then with a weight proportional to price
+export async function feedbackWeightedByPrice(ctx: TestContext) {
+  await setMintConfig(ctx, 1_000_000, Number(ctx.price) + 1);
+  const before = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+  await acceptRated(ctx, "feedbackBelowMinimum");
+  const cheap = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+  assert.equal(cheap.ratingsCount.toNumber(), before.ratingsCount.toNumber() + 1);
+  assert.equal(cheap.weightedRatingsCount.toNumber(), before.weightedRatingsCount.toNumber());
+
+  await setMintConfig(ctx, 500_000, 0);
+  await acceptRated(ctx, "feedbackWeighted");
+  const weighted = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+  const weight = (Number(ctx.price) * SCORE_UNIT) / 500_000;
+  assert.equal(weighted.ratingsCount.toNumber(), cheap.ratingsCount.toNumber() + 1);
+  assert.equal(weighted.weightedRatingsCount.toNumber(), cheap.weightedRatingsCount.toNumber() + weight);
+  assert.equal(weighted.weightedRatingsSum.toNumber(), cheap.weightedRatingsSum.toNumber() + 5 * weight);
+}
+
+// An order placed for the provider itself leaves its history untouched,
+// however it is rated
+export async function selfDealtOrderIgnored(ctx: TestContext) {
+  await mintTo(ctx.connection, ctx.user.payer, ctx.mint, ctx.userTokenAccount, ctx.user.payer, ctx.price);
+  const selfCtx = { ...ctx, beneficiary: ctx.service.publicKey };
+  const [serviceStatsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("client-stats"), ctx.service.publicKey.toBuffer()],
+    ctx.program.programId
+  );
+  const before = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(selfCtx, "selfDealtOrder", deadline);
+  await getDeliverTransaction({ ...selfCtx, orderPda, orderVaultTokenAccount }, ctx.service).rpc();
+  await ctx.program.methods
+    .acceptDelivery(5)
+    .accounts({
+      user: ctx.service.publicKey,
+      payer: ctx.user.publicKey,
+      payerTokenAccount: ctx.userTokenAccount,
+      order: orderPda,
+      orderVaultTokenAccount,
+      vaultAuthority: ctx.vaultAuthority,
+      vaultTokenAccount: ctx.vaultTokenAccount,
+      reputation: ctx.reputationPda,
+      clientStats: serviceStatsPda,
+      mintConfig: mintConfigPda(ctx),
+      bond: bondAccounts(ctx).bondPda,
+      config: ctx.configPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.service])
+    .rpc();
+
+  const order = await ctx.program.account.order.fetch(orderPda);
+  assert.isDefined(order.status.completed);
+  const after = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+  assert.equal(after.ordersCompleted.toNumber(), before.ordersCompleted.toNumber());
+  assert.equal(after.ordersAccepted.toNumber(), before.ordersAccepted.toNumber());
+  assert.equal(after.ratingsCount.toNumber(), before.ratingsCount.toNumber());
+  assert.equal(after.ratingsSum.toNumber(), before.ratingsSum.toNumber());
+  assert.equal(after.weightedRatingsCount.toNumber(), before.weightedRatingsCount.toNumber());
+  assert.equal(after.volume.toString(), before.volume.toString());
+  assert.equal(after.score, before.score);
+  const stats = await ctx.program.account.clientStats.fetch(serviceStatsPda);
+  assert.equal(stats.ordersPlaced.toNumber(), 0);
+  assert.equal(stats.ordersCompleted.toNumber(), 0);
+}
@@ -1,12 +1,12 @@
 import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
 import { assert } from "chai";
 import { setup, TestContext } from "./setup";
-import { deliverAfterDeadline, deliverSuccess, deliverUnauthorized, replayDeliver, wrongOrderAccount, wrongTokenAccountOwner } from "./deliver";
-import { withdraw, withdrawWrongAuthority, withdrawWrongConfig } from "./withdraw";
+import { deliverAfterDeadline, deliverSuccess, deliverUnauthorized, finalizeSuccess, replayDeliver, wrongOrderAccount, wrongTokenAccountOwner } from "./deliver";
+import { withdraw, withdrawWrongAuthority, withdrawWrongVault } from "./withdraw";
 import { commitWrongSignature } from "./commit/invalid_signature.test";
 import { commitWrongInstruction } from "./commit/invalid_signature.test";
 import { commitMissingInstruction } from "./commit/invalid_signature.test";
-import { commitWrongMint, commitWrongTokenAccountOwner } from "./commit/wrong_data.test";
+import { commitUnsupportedVersion, commitWrongMint, commitWrongTokenAccountOwner } from "./commit/wrong_data.test";
 import { commitInsufficientFunds } from "./commit/wrong_data.test";
 import { commitSuccess } from "./commit/valid.test";
 import { commitReplayAttack } from "./commit/replay.test";
@@ -15,6 +15,32 @@ import { commitExpiredPrice } from "./commit/expired_price.test";
 import { declineSuccess } from "./decline/valid.test";
 import { refundSuccess } from "./refund/valid.test";
 import { refundBeforeDeadline } from "./refund/before_deadline.test";
+import { rfqOverBudget, rfqSuccess } from "./rfq/valid.test";
+import { bidOverBudget, bidSuccess } from "./bidding/valid.test";
+import { challengeWindowNeedsDisputePeriod, disputeRefundedAfterTimeout, disputeResolvedByDecline, finalizeDuringChallengeWindow } from "./dispute/valid.test";
+import { acceptDeliveryInvalidRating, acceptDeliverySuccess } from "./accept/valid.test";
+import { revealKeySuccess, revealWrongKey } from "./fair_exchange/valid.test";
+import { drawCreditSuccess, drawStaleReceipt, withdrawCreditSuccess } from "./credit/valid.test";
+import { closeChannelSuccess, closeChannelWithoutPayment, forceCloseBeforeTimeout } from "./channel/valid.test";
+import { cancelRefundsFuturePeriods, chargeDelegated, chargePrefunded } from "./subscription/valid.test";
+import { claimNonVestingOrder, claimVestedThenStop, vestingCompletesWhenFullyVested, vestingEncryptedRefundedWhenKeyWithheld } from "./vesting/valid.test";
+import { deliverAfterGrace, lateDeliveryWithinGrace } from "./late/valid.test";
+import { bonusEarned, bonusForfeited } from "./bonus/valid.test";
+import { tipBeforeCompletion, tipSuccess } from "./tip/valid.test";
+import { amendOrderReplay, amendOrderSuccess, amendVestingDeadline } from "./amend/valid.test";
+import { revisionPastDeadlineNotLate, revisionThenAccept } from "./revision/valid.test";
+import { offerBoundToBeneficiary, treasuryPaysAndGetsRefund } from "./beneficiary/valid.test";
+import { relayAndSubscriptionShareAccount, relayedCommitSuccess } from "./relay/valid.test";
+import { bondCapacityExceeded, bondHeldUntilAccepted, bondLockedOnEvaluationCommit, bondRelockedOnAmendment, bondSlashedOnRefund } from "./bond/valid.test";
+import { depositForfeitedAfterDispute, depositReturnedOnFinalize } from "./deposit/valid.test";
+import { unsatisfiedClientRefused } from "./client/valid.test";
+import { scoreDecaysOverTime, scoreTracksCompletionRate } from "./score/valid.test";
+import { feedbackWeightedByPrice, selfDealtOrderIgnored } from "./feedback/valid.test";
+import { reputationViewMatchesAccount, reputationViewOfUnknownProvider } from "./reputation/valid.test";
+import { treasuryBondedOrderRefunded, treasuryOrderRefunded } from "./treasury/valid.test";
+import { receiptMintedToBothParties } from "./receipt/valid.test";
+import { profileCreatedAndUpdated, profileSignerFromConfig } from "./profile/valid.test";
+import { migrationRejectsCurrentAccounts } from "./migration/valid.test";
 
 describe("gateway", () => {
   // Configure the client to use the local cluster.
@@ -45,6 +71,10 @@ describe("gateway", () => {
       await commitWrongJobHash(ctx);
     });
 
+    it("Unsupported offer version", async () => {
+      await commitUnsupportedVersion(ctx);
+    });
+
     it("Wrong token account owner", async () => {
       await commitWrongTokenAccountOwner(ctx);
     });
@@ -85,14 +115,17 @@ describe("gateway", () => {
     it("Replay deliver", async () => {
       await replayDeliver(ctx);
     });
+    it("Finalizes after the challenge window", async () => {
+      await finalizeSuccess(ctx);
+    });
   });
 
   describe("Withdraw", async () => {
     it("Wrong authority", async () => {
       await withdrawWrongAuthority(ctx);
     });
-    it("Wrong config", async () => {
-      await withdrawWrongConfig(ctx);
+    it("Wrong vault", async () => {
+      await withdrawWrongVault(ctx);
     });
     it("Withdraws tokens from vault", async () => {
       await withdraw(ctx);
@@ -120,4 +153,411 @@ describe("gateway", () => {
       await refundSuccess(ctx);
     });
   });
+
+  describe("Request for quote", async () => {
+    let ctx_rfq: TestContext;
+
+    before(async () => {
+      ctx_rfq = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Evaluation over budget", async () => {
+      await rfqOverBudget(ctx_rfq);
+    });
+    it("Commits an on-chain evaluation", async () => {
+      await rfqSuccess(ctx_rfq);
+    });
+  });
+
+  describe("Bidding", async () => {
+    let ctx_bidding: TestContext;
+
+    before(async () => {
+      ctx_bidding = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Bid over budget", async () => {
+      await bidOverBudget(ctx_bidding);
+    });
+    it("Accepts a winning bid", async () => {
+      await bidSuccess(ctx_bidding);
+    });
+  });
+
+  describe("Challenge window", async () => {
+    let ctx_dispute: TestContext;
+
+    before(async () => {
+      ctx_dispute = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Finalize during challenge window", async () => {
+      await finalizeDuringChallengeWindow(ctx_dispute);
+    });
+    it("Dispute resolved by decline", async () => {
+      await disputeResolvedByDecline(ctx_dispute);
+    });
+    it("Unanswered dispute is refunded", async () => {
+      await disputeRefundedAfterTimeout(ctx_dispute);
+    });
+    it("Challenge window requires a dispute period", async () => {
+      await challengeWindowNeedsDisputePeriod(ctx_dispute);
+    });
+    it("Invalid rating", async () => {
+      await acceptDeliveryInvalidRating(ctx_dispute);
+    });
+    it("Client accepts delivery", async () => {
+      await acceptDeliverySuccess(ctx_dispute);
+    });
+  });
+
+  describe("Fair exchange", async () => {
+    let ctx_fair_exchange: TestContext;
+
+    before(async () => {
+      ctx_fair_exchange = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Reveal wrong key", async () => {
+      await revealWrongKey(ctx_fair_exchange);
+    });
+    it("Key reveal releases payment", async () => {
+      await revealKeySuccess(ctx_fair_exchange);
+    });
+  });
+
+  describe("Prepaid credit", async () => {
+    let ctx_credit: TestContext;
+
+    before(async () => {
+      ctx_credit = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Draws with usage receipts", async () => {
+      await drawCreditSuccess(ctx_credit);
+    });
+    it("Stale receipt", async () => {
+      await drawStaleReceipt(ctx_credit);
+    });
+    it("Withdraws the unused balance", async () => {
+      await withdrawCreditSuccess(ctx_credit);
+    });
+  });
+
+  describe("Payment channel", async () => {
+    let ctx_channel: TestContext;
+
+    before(async () => {
+      ctx_channel = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Closes with the latest voucher", async () => {
+      await closeChannelSuccess(ctx_channel);
+    });
+    it("Zero-amount voucher earns no reputation", async () => {
+      await closeChannelWithoutPayment(ctx_channel);
+    });
+    it("Force close before timeout", async () => {
+      await forceCloseBeforeTimeout(ctx_channel);
+    });
+  });
+
+  describe("Subscription", async () => {
+    let ctx_subscription: TestContext;
+
+    before(async () => {
+      ctx_subscription = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Charges a prefunded period", async () => {
+      await chargePrefunded(ctx_subscription);
+    });
+    it("Charges through a delegate approval", async () => {
+      await chargeDelegated(ctx_subscription);
+    });
+    it("Cancel refunds future periods", async () => {
+      await cancelRefundsFuturePeriods(ctx_subscription);
+    });
+  });
+
+  describe("Vesting", async () => {
+    let ctx_vesting: TestContext;
+
+    before(async () => {
+      ctx_vesting = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Claims vested escrow then stops the stream", async () => {
+      await claimVestedThenStop(ctx_vesting);
+    });
+    it("Claim on a non-vesting order", async () => {
+      await claimNonVestingOrder(ctx_vesting);
+    });
+    it("Completes a fully vested order", async () => {
+      await vestingCompletesWhenFullyVested(ctx_vesting);
+    });
+    it("Refunds a vesting order whose key is never revealed", async () => {
+      await vestingEncryptedRefundedWhenKeyWithheld(ctx_vesting);
+    });
+  });
+
+  describe("Late delivery", async () => {
+    let ctx_late: TestContext;
+
+    before(async () => {
+      ctx_late = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Delivers within the grace period at a reduced price", async () => {
+      await lateDeliveryWithinGrace(ctx_late);
+    });
+    it("Deliver after the grace period", async () => {
+      await deliverAfterGrace(ctx_late);
+    });
+  });
+
+  describe("Bonus", async () => {
+    let ctx_bonus: TestContext;
+
+    before(async () => {
+      ctx_bonus = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Pays the bonus for an early delivery", async () => {
+      await bonusEarned(ctx_bonus);
+    });
+    it("Returns the bonus for a delivery after the threshold", async () => {
+      await bonusForfeited(ctx_bonus);
+    });
+  });
+
+  describe("Tip", async () => {
+    let ctx_tip: TestContext;
+
+    before(async () => {
+      ctx_tip = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Tips a completed order", async () => {
+      await tipSuccess(ctx_tip);
+    });
+    it("Tip before completion", async () => {
+      await tipBeforeCompletion(ctx_tip);
+    });
+  });
+
+  describe("Amend order", async () => {
+    let ctx_amend: TestContext;
+
+    before(async () => {
+      ctx_amend = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Raises the price and moves the deadline", async () => {
+      await amendOrderSuccess(ctx_amend);
+    });
+    it("Replay amendment", async () => {
+      await amendOrderReplay(ctx_amend);
+    });
+    it("Vesting deadline cannot move", async () => {
+      await amendVestingDeadline(ctx_amend);
+    });
+  });
+
+  describe("Revision", async () => {
+    let ctx_revision: TestContext;
+
+    before(async () => {
+      ctx_revision = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Requests a revision and accepts the redelivery", async () => {
+      await revisionThenAccept(ctx_revision);
+    });
+    it("Redelivery past the deadline is not late", async () => {
+      await revisionPastDeadlineNotLate(ctx_revision);
+    });
+  });
+
+  describe("Beneficiary", async () => {
+    let ctx_beneficiary: TestContext;
+
+    before(async () => {
+      ctx_beneficiary = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Treasury pays for an order and gets the refund", async () => {
+      await treasuryPaysAndGetsRefund(ctx_beneficiary);
+    });
+    it("Offer is bound to its client", async () => {
+      await offerBoundToBeneficiary(ctx_beneficiary);
+    });
+  });
+
+  describe("Relayed commit", async () => {
+    let ctx_relay: TestContext;
+
+    before(async () => {
+      ctx_relay = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Relayer commits on behalf of the client", async () => {
+      await relayedCommitSuccess(ctx_relay);
+    });
+    it("Relay and subscription approvals do not overwrite each other", async () => {
+      await relayAndSubscriptionShareAccount(ctx_relay);
+    });
+  });
+
+  describe("Bond", async () => {
+    let ctx_bond: TestContext;
+
+    before(async () => {
+      ctx_bond = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Slashes the bond on refund", async () => {
+      await bondSlashedOnRefund(ctx_bond);
+    });
+    it("Bond caps outstanding work", async () => {
+      await bondCapacityExceeded(ctx_bond);
+    });
+    it("Locks the bond on evaluation commits", async () => {
+      await bondLockedOnEvaluationCommit(ctx_bond);
+    });
+    it("Holds the bond until the delivery is accepted", async () => {
+      await bondHeldUntilAccepted(ctx_bond);
+    });
+    it("Locks the bond for an amended price", async () => {
+      await bondRelockedOnAmendment(ctx_bond);
+    });
+  });
+
+  describe("Client deposit", async () => {
+    let ctx_deposit: TestContext;
+
+    before(async () => {
+      ctx_deposit = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Returns the deposit on finalize", async () => {
+      await depositReturnedOnFinalize(ctx_deposit);
+    });
+    it("Forfeits the deposit after an unfounded dispute", async () => {
+      await depositForfeitedAfterDispute(ctx_deposit);
+    });
+  });
+
+  describe("Client stats", async () => {
+    let ctx_client: TestContext;
+
+    before(async () => {
+      ctx_client = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Offer refuses a client with too many unsatisfied verdicts", async () => {
+      await unsatisfiedClientRefused(ctx_client);
+    });
+  });
+
+  describe("Reputation score", async () => {
+    let ctx_score: TestContext;
+
+    before(async () => {
+      ctx_score = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Score follows the completion rate", async () => {
+      await scoreTracksCompletionRate(ctx_score);
+    });
+    it("Score decays with time", async () => {
+      await scoreDecaysOverTime(ctx_score);
+    });
+  });
+
+  describe("Weighted feedback", async () => {
+    let ctx_feedback: TestContext;
+
+    before(async () => {
+      ctx_feedback = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Weights ratings by price and ignores cheap orders", async () => {
+      await feedbackWeightedByPrice(ctx_feedback);
+    });
+    it("Ignores orders the provider placed with itself", async () => {
+      await selfDealtOrderIgnored(ctx_feedback);
+    });
+  });
+
+  describe("Reputation view", async () => {
+    let ctx_view: TestContext;
+
+    before(async () => {
+      ctx_view = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Returns the provider's reputation", async () => {
+      await reputationViewMatchesAccount(ctx_view);
+    });
+    it("Returns zeros for a provider without history", async () => {
+      await reputationViewOfUnknownProvider(ctx_view);
+    });
+  });
+
+  describe("PDA clients", async () => {
+    let ctx_treasury: TestContext;
+
+    before(async () => {
+      ctx_treasury = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Program treasury orders and is refunded through CPI", async () => {
+      await treasuryOrderRefunded(ctx_treasury);
+    });
+
+    it("Program treasury gets a bonded order refunded", async () => {
+      await treasuryBondedOrderRefunded(ctx_treasury);
+    });
+  });
+
+  describe("Receipts", async () => {
+    let ctx_receipt: TestContext;
+
+    before(async () => {
+      ctx_receipt = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Mints a soulbound receipt to both parties", async () => {
+      await receiptMintedToBothParties(ctx_receipt);
+    });
+  });
+
+  describe("Provider profile", async () => {
+    let ctx_profile: TestContext;
+
+    before(async () => {
+      ctx_profile = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Provider creates and updates its profile", async () => {
+      await profileCreatedAndUpdated(ctx_profile);
+    });
+
+    it("Deployment authority lists the config signer", async () => {
+      await profileSignerFromConfig(ctx_profile);
+    });
+  });
+
+  describe("Migration", async () => {
+    let ctx_migration: TestContext;
+
+    before(async () => {
+      ctx_migration = await setup({ service: ctx.service, run_initialize: false });
+    });
+
+    it("Leaves accounts in the current layout alone", async () => {
+      await migrationRejectsCurrentAccounts(ctx_migration);
+    });
+  });
 });
@@ -0,0 +1,15 @@
+import { PublicKey } from "@solana/web3.js";
+import { TestContext } from "../setup";
+
+// The provider's bond and its vault for the context's mint
+export function bondAccounts(ctx: TestContext, provider: PublicKey = ctx.service.publicKey) {
+  const [bondPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("bond"), provider.toBuffer(), ctx.mint.toBuffer()],
+    ctx.program.programId
+  );
+  const [bondVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("bond-vault"), provider.toBuffer(), ctx.mint.toBuffer()],
+    ctx.program.programId
+  );
+  return { bondPda, bondVaultTokenAccount };
+}
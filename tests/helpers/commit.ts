@@ -2,17 +2,27 @@ import * as anchor from "@coral-xyz/anchor";
 import { createMint, getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import nacl from "tweetnacl";
 import { TestContext } from "../setup";
-import { Order, serializeOrder } from "../utils";
+import { bondAccounts } from "./bond";
+import { Order, OFFER_VERSION, serializeOrder } from "../utils";
 import { ecsign, keccak256, privateToAddress } from "ethereumjs-util";
 import { PublicKey } from "@solana/web3.js";
 
 export interface CommitOrderData {
+  version?: number;
   user?: Uint8Array;
   job_hash?: Uint8Array;
   price?: bigint;
   mint?: Uint8Array;
   price_valid_until?: bigint;
   deadline?: bigint;
+  vesting?: boolean;
+  grace_period?: bigint;
+  penalty_bps_per_hour?: number;
+  bonus?: bigint;
+  bonus_deadline?: bigint;
+  relayer_fee?: bigint;
+  client_deposit?: bigint;
+  max_client_unsatisfied_bps?: number;
 }
 
 const ONE_DAY_IN_MS = 1000 * 60 * 60 * 24;
@@ -22,12 +32,21 @@ export async function buildOrderMessage(
   overrides: CommitOrderData = {}
 ): Promise<Uint8Array> {
   const orderData = new Order({
-    user: overrides.user || testCtx.user.publicKey.toBytes(),
+    version: overrides.version ?? OFFER_VERSION,
+    user: overrides.user || (testCtx.beneficiary || testCtx.user.publicKey).toBytes(),
     job_hash: overrides.job_hash || new Uint8Array(testCtx.jobHash),
     price: overrides.price || testCtx.price,
     mint: overrides.mint || new Uint8Array(testCtx.mint.toBuffer()),
     price_valid_until: overrides.price_valid_until || BigInt(Date.now() + ONE_DAY_IN_MS),
     deadline: overrides.deadline || BigInt(Math.floor(Date.now() / 1000) + 2 * ONE_DAY_IN_MS),
+    vesting: overrides.vesting || false,
+    grace_period: overrides.grace_period || BigInt(0),
+    penalty_bps_per_hour: overrides.penalty_bps_per_hour || 0,
+    bonus: overrides.bonus || BigInt(0),
+    bonus_deadline: overrides.bonus_deadline || BigInt(0),
+    relayer_fee: overrides.relayer_fee || BigInt(0),
+    client_deposit: overrides.client_deposit || BigInt(0),
+    max_client_unsatisfied_bps: overrides.max_client_unsatisfied_bps || 0,
   });
 
   return serializeOrder(orderData);
@@ -58,11 +77,13 @@ export async function buildCommitTransaction(
         .commit(Array.from(testCtx.jobHash)) // Convert Buffer to number array
         .accounts({
           user: testCtx.user.publicKey,
+          beneficiary: testCtx.beneficiary || testCtx.user.publicKey,
           order: testCtx.orderPda,
           userTokenAccount: testCtx.userTokenAccount,
           orderVaultTokenAccount: testCtx.orderVaultTokenAccount,
           mint: testCtx.mint,
           config: testCtx.configPda,
+          bond: bondAccounts(testCtx).bondPda,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -97,11 +118,13 @@ export async function buildSecp256k1CommitTransaction(
         .commit(Array.from(testCtx.jobHash)) // Convert Buffer to number array
         .accounts({
           user: testCtx.user.publicKey,
+          beneficiary: testCtx.beneficiary || testCtx.user.publicKey,
           order: testCtx.orderPda,
           userTokenAccount: testCtx.userTokenAccount,
           orderVaultTokenAccount: testCtx.orderVaultTokenAccount,
           mint: testCtx.mint,
           config: testCtx.configPda,
+          bond: bondAccounts(testCtx).bondPda,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
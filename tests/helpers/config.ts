@@ -0,0 +1,33 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TestContext } from "../setup";
+import { ONE_DAY_IN_SECONDS } from "./order";
+
+export const DEFAULT_CONFIG_PARAMS = {
+    challengeWindow: new anchor.BN(0),
+    creditNoticePeriod: new anchor.BN(0),
+    maxRevisions: 0,
+    revisionPeriod: new anchor.BN(0),
+    bondSlashBps: 0,
+    bondLeverage: 0,
+    score: {
+        halfLife: new anchor.BN(0),
+        completionWeight: 0,
+        timelinessWeight: 0,
+        satisfactionWeight: 0,
+        refundWeight: 0,
+        volumeWeight: 0,
+        volumeTarget: new anchor.BN(0),
+    },
+    // Required whenever a test opens a challenge window
+    disputePeriod: new anchor.BN(ONE_DAY_IN_SECONDS),
+};
+
+export function getUpdateConfigTransaction(ctx: TestContext, overrides: any = {}) {
+    return ctx.program.methods
+      .updateConfig({ ...DEFAULT_CONFIG_PARAMS, ...overrides })
+      .accounts({
+        authority: ctx.service.publicKey,
+        config: ctx.configPda,
+      })
+      .signers([ctx.service])
+}
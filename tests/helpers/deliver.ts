@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { TestContext } from "../setup";
 import { Keypair } from "@solana/web3.js";
+import { bondAccounts } from "./bond";
 
 export function getDeliverTransaction(ctx: TestContext, signer: Keypair, overrides: any = {}) {
     return ctx.program.methods
@@ -10,13 +11,54 @@ export function getDeliverTransaction(ctx: TestContext, signer: Keypair, overrid
         authority: signer.publicKey,
         order: ctx.orderPda,
         orderVaultTokenAccount: ctx.orderVaultTokenAccount,
+        userTokenAccount: ctx.userTokenAccount,
+        config: ctx.configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...overrides
+      })
+      .signers([signer])
+}
+
+export function getFinalizeTransaction(ctx: TestContext, payer: Keypair, overrides: any = {}) {
+    return ctx.program.methods
+      .finalize()
+      .accounts({
+        payer: payer.publicKey,
+        user: ctx.user.publicKey,
+        userTokenAccount: ctx.userTokenAccount,
+        order: ctx.orderPda,
+        orderVaultTokenAccount: ctx.orderVaultTokenAccount,
         vaultAuthority: ctx.vaultAuthority,
         vaultTokenAccount: ctx.vaultTokenAccount,
+        reputation: ctx.reputationPda,
+        bond: bondAccounts(ctx).bondPda,
         config: ctx.configPda,
         mint: ctx.mint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
         ...overrides
       })
-      .signers([signer])
+      .signers([payer])
+}
+
+export function getAcceptDeliveryTransaction(ctx: TestContext, rating: number | null, overrides: any = {}) {
+    return ctx.program.methods
+      .acceptDelivery(rating)
+      .accounts({
+        user: ctx.user.publicKey,
+        payer: ctx.user.publicKey,
+        payerTokenAccount: ctx.userTokenAccount,
+        order: ctx.orderPda,
+        orderVaultTokenAccount: ctx.orderVaultTokenAccount,
+        vaultAuthority: ctx.vaultAuthority,
+        vaultTokenAccount: ctx.vaultTokenAccount,
+        reputation: ctx.reputationPda,
+        bond: bondAccounts(ctx).bondPda,
+        config: ctx.configPda,
+        mint: ctx.mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        ...overrides
+      })
+      .signers([ctx.user.payer])
 }
@@ -1,23 +1,30 @@
 import { TestContext } from "../setup";
-import { buildCommitTransaction, buildOrderMessage, prepareAndSubmitTransaction, signEd25519 } from "./commit";
+import { buildCommitTransaction, buildOrderMessage, CommitOrderData, prepareAndSubmitTransaction, signEd25519 } from "./commit";
 import { PublicKey } from "@solana/web3.js";
 import Keccak from 'keccak';
 
+export const ONE_DAY_IN_SECONDS = 60 * 60 * 24;
+
 export interface OrderAccounts {
   orderPda: PublicKey;
   orderVaultTokenAccount: PublicKey;
 }
 
-export async function commitOrder(ctx: TestContext, job_name: string, deadline?: bigint): Promise<OrderAccounts> {
-  const ONE_DAY_IN_MS = 1000 * 60 * 60 * 24;
+export async function commitOrder(
+  ctx: TestContext,
+  job_name: string,
+  deadline?: bigint,
+  overrides: CommitOrderData = {}
+): Promise<OrderAccounts> {
   const job_hash = Keccak('keccak256').update(job_name).digest();
   const message = await buildOrderMessage(ctx, {
+    ...overrides,
     job_hash,
-    deadline: deadline || BigInt(Math.floor(Date.now() / 1000) - ONE_DAY_IN_MS)
+    deadline: deadline || BigInt(Math.floor(Date.now() / 1000) - ONE_DAY_IN_SECONDS)
   });
   const signature = await signEd25519(message, ctx.service.secretKey);
   const [orderPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("order"), ctx.user.publicKey.toBuffer(), job_hash],
+    [Buffer.from("order"), (ctx.beneficiary || ctx.user.publicKey).toBuffer(), job_hash],
     ctx.program.programId
   );
   const [orderVaultTokenAccount] = PublicKey.findProgramAddressSync(
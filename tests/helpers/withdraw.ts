@@ -7,7 +7,6 @@ export function buildWithdrawTransaction(ctx: TestContext, overrides: any = {})
         .withdraw()
         .accounts({
             authority:  ctx.service.publicKey,
-            config: ctx.configPda,
             vaultTokenAccount: ctx.vaultTokenAccount,
             recipientTokenAccount: ctx.recipientTokenAccount,
             vaultAuthority: ctx.vaultAuthority,
@@ -0,0 +1,59 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { TestContext } from "../setup";
+import { commitOrder } from "../helpers/order";
+import { getDeliverTransaction, getFinalizeTransaction } from "../helpers/deliver";
+
+const ONE_HOUR_IN_SECONDS = 60 * 60;
+const PENALTY_BPS_PER_HOUR = 1_000;
+
+export async function lateDeliveryWithinGrace(ctx: TestContext) {
+  const { amount: userBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  const { amount: vaultBefore } = await getAccount(ctx.connection, ctx.vaultTokenAccount)
+    .catch(() => ({ amount: BigInt(0) }));
+
+  // One hour past the deadline, inside a two hour grace period
+  const deadline = BigInt(Math.floor(Date.now() / 1000) - ONE_HOUR_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "lateDeliveryWithinGrace", deadline, {
+    grace_period: BigInt(2 * ONE_HOUR_IN_SECONDS),
+    penalty_bps_per_hour: PENALTY_BPS_PER_HOUR,
+  });
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+
+  const order = await ctx.program.account.order.fetch(orderPda);
+  assert.isDefined(order.status.delivered);
+  const penalty = BigInt(order.latePenalty.toString());
+  const oneHourPenalty = ctx.price * BigInt(PENALTY_BPS_PER_HOUR) / BigInt(10_000);
+  assert.isTrue(penalty >= oneHourPenalty, "Penalty should cover at least one hour");
+  assert.isTrue(penalty < 2n * oneHourPenalty, "Penalty should be under two hours");
+
+  // The client got the penalty back right away
+  const { amount: userAfterDeliver } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(userAfterDeliver, userBefore - ctx.price + penalty);
+
+  await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+  const { amount: vaultAfter } = await getAccount(ctx.connection, ctx.vaultTokenAccount);
+  assert.equal(vaultAfter - vaultBefore, ctx.price - penalty);
+
+  const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+  assert.isTrue(reputation.ordersLate.gtn(0));
+}
+
+export async function deliverAfterGrace(ctx: TestContext) {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) - 2 * ONE_HOUR_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "deliverAfterGrace", deadline, {
+    grace_period: BigInt(ONE_HOUR_IN_SECONDS),
+    penalty_bps_per_hour: PENALTY_BPS_PER_HOUR,
+  });
+
+  try {
+    await getDeliverTransaction({ ...ctx, orderPda, orderVaultTokenAccount }, ctx.service).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "DeliverAfterDeadline");
+  }
+}
@@ -0,0 +1,47 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+
+function assertAlreadyMigrated(error: any) {
+  assert.isTrue(error instanceof anchor.AnchorError);
+  const err: anchor.AnchorError = error;
+  assert.strictEqual(err.error.errorCode.code, "AlreadyMigrated");
+}
+
+export async function migrationRejectsCurrentAccounts(ctx: TestContext) {
+  try {
+    await ctx.program.methods
+      .migrateConfig()
+      .accounts({
+        authority: ctx.service.publicKey,
+        config: ctx.configPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.service])
+      .rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assertAlreadyMigrated(error);
+  }
+
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "migrationRejectsCurrentAccounts", deadline);
+  try {
+    await ctx.program.methods
+      .migrateOrder()
+      .accounts({
+        payer: ctx.user.publicKey,
+        order: orderPda,
+        orderVaultTokenAccount,
+        config: ctx.configPda,
+        mint: ctx.mint,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.user.payer])
+      .rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assertAlreadyMigrated(error);
+  }
+}
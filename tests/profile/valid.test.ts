@@ -0,0 +1,79 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { registerProvider } from "../bidding/valid.test";
+
+function profilePda(ctx: TestContext, authority: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("profile"), authority.toBuffer()],
+    ctx.program.programId
+  );
+  return pda;
+}
+
+function setProfile(ctx: TestContext, authority: Keypair, provider: PublicKey | null, overrides: any = {}) {
+  return ctx.program.methods
+    .setProfile({
+      name: "Test provider",
+      tags: ["inference", "gpu"],
+      metadataUri: "https://example.com/provider.json",
+      quoteEndpoint: "https://example.com/quote",
+      acceptedMints: [ctx.mint],
+      ...overrides,
+    })
+    .accounts({ authority: authority.publicKey, provider, config: ctx.configPda })
+    .signers([authority]);
+}
+
+export async function profileCreatedAndUpdated(ctx: TestContext) {
+  const authority = Keypair.generate();
+  const { providerPda } = await registerProvider(ctx, authority);
+  const pda = profilePda(ctx, authority.publicKey);
+
+  await setProfile(ctx, authority, providerPda).rpc();
+  let profile = await ctx.program.account.providerProfile.fetch(pda);
+  assert.ok(profile.authority.equals(authority.publicKey));
+  // registerProvider makes the authority its own signer
+  assert.ok(profile.signer.equals(authority.publicKey));
+  assert.equal(profile.name, "Test provider");
+  assert.deepEqual(profile.tags, ["inference", "gpu"]);
+  assert.equal(profile.quoteEndpoint, "https://example.com/quote");
+  assert.ok(profile.acceptedMints[0].equals(ctx.mint));
+
+  await setProfile(ctx, authority, providerPda, {
+    quoteEndpoint: "https://example.org/quote",
+  }).rpc();
+  profile = await ctx.program.account.providerProfile.fetch(pda);
+  assert.equal(profile.quoteEndpoint, "https://example.org/quote");
+
+  try {
+    await setProfile(ctx, authority, providerPda, { name: "x".repeat(33) }).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "ProfileFieldTooLong");
+  }
+}
+
+// The deployment authority lists the config's offer signer, anyone else
+// has to register as a provider first
+export async function profileSignerFromConfig(ctx: TestContext) {
+  await setProfile(ctx, ctx.service, null).rpc();
+  const profile = await ctx.program.account.providerProfile.fetch(profilePda(ctx, ctx.service.publicKey));
+  const config = await ctx.program.account.config.fetch(ctx.configPda);
+  assert.ok(profile.signer.equals(config.authoritySigner));
+
+  const stranger = Keypair.generate();
+  const sig = await ctx.connection.requestAirdrop(stranger.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+  await ctx.connection.confirmTransaction(sig, "confirmed");
+  try {
+    await setProfile(ctx, stranger, null).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "InvalidProvider");
+  }
+}
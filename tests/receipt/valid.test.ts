@@ -0,0 +1,108 @@
+import { assert } from "chai";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getTokenMetadata,
+  transfer,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getAcceptDeliveryTransaction, getDeliverTransaction } from "../helpers/deliver";
+
+function getMintReceiptTransaction(ctx: TestContext, orderPda: PublicKey, payer: Keypair = ctx.user.payer) {
+  const [receiptMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("receipt"), orderPda.toBuffer()],
+    ctx.program.programId
+  );
+  return ctx.program.methods
+    .mintReceipt()
+    .accounts({
+      payer: payer.publicKey,
+      order: orderPda,
+      client: ctx.user.publicKey,
+      clientReceiptAccount: getAssociatedTokenAddressSync(receiptMint, ctx.user.publicKey, false, TOKEN_2022_PROGRAM_ID),
+      provider: ctx.service.publicKey,
+      providerReceiptAccount: getAssociatedTokenAddressSync(receiptMint, ctx.service.publicKey, false, TOKEN_2022_PROGRAM_ID),
+    })
+    .signers([payer]);
+}
+
+export async function receiptMintedToBothParties(ctx: TestContext) {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "receiptCompleted", deadline);
+  const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  await getAcceptDeliveryTransaction(orderCtx, null).rpc();
+
+  const [receiptMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("receipt"), orderPda.toBuffer()],
+    ctx.program.programId
+  );
+  const clientReceipt = getAssociatedTokenAddressSync(receiptMint, ctx.user.publicKey, false, TOKEN_2022_PROGRAM_ID);
+  const providerReceipt = getAssociatedTokenAddressSync(receiptMint, ctx.service.publicKey, false, TOKEN_2022_PROGRAM_ID);
+
+  // Only the order's own parties may mint its receipt
+  const stranger = Keypair.generate();
+  const sig = await ctx.connection.requestAirdrop(stranger.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+  await ctx.connection.confirmTransaction(sig, "confirmed");
+  try {
+    await getMintReceiptTransaction(ctx, orderPda, stranger).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "InvalidUser");
+  }
+
+  // Lamports sent to the receipt address beforehand must not block the mint
+  await anchor.web3.sendAndConfirmTransaction(
+    ctx.connection,
+    new anchor.web3.Transaction().add(
+      SystemProgram.transfer({ fromPubkey: stranger.publicKey, toPubkey: receiptMint, lamports: 1_000_000 })
+    ),
+    [stranger]
+  );
+  await getMintReceiptTransaction(ctx, orderPda).rpc();
+
+  for (const account of [clientReceipt, providerReceipt]) {
+    const { amount } = await getAccount(ctx.connection, account, "confirmed", TOKEN_2022_PROGRAM_ID);
+    assert.equal(amount, BigInt(1));
+  }
+
+  const metadata = await getTokenMetadata(ctx.connection, receiptMint, "confirmed", TOKEN_2022_PROGRAM_ID);
+  const fields = Object.fromEntries(metadata.additionalMetadata);
+  assert.equal(fields.order, orderPda.toBase58());
+  assert.equal(fields.result_hash, ctx.resultHash.toString("hex"));
+  assert.equal(fields.outcome, "completed");
+
+  // Soulbound: the client cannot pass its receipt on
+  try {
+    await transfer(
+      ctx.connection,
+      ctx.user.payer,
+      clientReceipt,
+      providerReceipt,
+      ctx.user.payer,
+      1,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.fail("Should have failed");
+  } catch (error) {
+    // TokenError::NonTransferable
+    assert.include(error.toString(), "0x25");
+  }
+
+  // One receipt per order
+  try {
+    await getMintReceiptTransaction(ctx, orderPda).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    // The receipt mint is already allocated
+    assert.include(error.toString(), "0x0");
+  }
+}
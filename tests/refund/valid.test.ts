@@ -15,6 +15,7 @@ export async function refundSuccess(ctx: TestContext) {
         order: orderPda,
         userTokenAccount: ctx.userTokenAccount,
         orderVaultTokenAccount,
+        reputation: ctx.reputationPda,
         mint: ctx.mint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
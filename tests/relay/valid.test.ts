@@ -0,0 +1,165 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { approve, getAccount, getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey, sendAndConfirmTransaction } from "@solana/web3.js";
+import { createHash } from "crypto";
+import Keccak from 'keccak';
+import { TestContext } from "../setup";
+import { bondAccounts } from "../helpers/bond";
+import { buildOrderMessage, signEd25519 } from "../helpers/commit";
+import { cancelSubscription, createSubscription, PRICE_PER_PERIOD } from "../subscription/valid.test";
+import { ONE_DAY_IN_SECONDS } from "../helpers/order";
+
+const RELAYER_FEE = BigInt(5_000);
+
+async function fundRelayer(ctx: TestContext) {
+  const relayer = Keypair.generate();
+  const sig = await ctx.connection.requestAirdrop(relayer.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
+  await ctx.connection.confirmTransaction(sig, "confirmed");
+  const relayerTokenAccount = await getOrCreateAssociatedTokenAccount(
+    ctx.connection,
+    relayer,
+    ctx.mint,
+    relayer.publicKey
+  );
+  return { relayer, relayerTokenAccount: relayerTokenAccount.address };
+}
+
+function relayAuthorityPda(ctx: TestContext): PublicKey {
+  const [relayAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("relay-authority")],
+    ctx.program.programId
+  );
+  return relayAuthority;
+}
+
+function approveRelay(ctx: TestContext) {
+  return approve(
+    ctx.connection,
+    ctx.user.payer,
+    ctx.userTokenAccount,
+    relayAuthorityPda(ctx),
+    ctx.user.payer,
+    ctx.price + RELAYER_FEE
+  );
+}
+
+// Submits a relayed commit signed by the client but paid for by `relayer`
+async function relayCommit(ctx: TestContext, job_name: string, relayer: Keypair, relayerTokenAccount: PublicKey) {
+  const job_hash = Keccak('keccak256').update(job_name).digest();
+  const offer = await buildOrderMessage(ctx, {
+    job_hash,
+    deadline: BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS),
+    relayer_fee: RELAYER_FEE,
+  });
+  const offerSignature = await signEd25519(offer, ctx.service.secretKey);
+  // The authorization is just the hash of the offer the client agrees to
+  const authorization = createHash("sha256").update(offer).digest();
+  const authorizationSignature = await signEd25519(authorization, ctx.user.payer.secretKey);
+
+  const [orderPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("order"), ctx.user.publicKey.toBuffer(), job_hash],
+    ctx.program.programId
+  );
+  const [orderVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), orderPda.toBuffer()],
+    ctx.program.programId
+  );
+
+  const tx = new anchor.web3.Transaction()
+    .add(
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: ctx.service.publicKey.toBytes(),
+        message: offer,
+        signature: offerSignature,
+      })
+    )
+    .add(
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: ctx.user.publicKey.toBytes(),
+        message: authorization,
+        signature: authorizationSignature,
+      })
+    )
+    .add(
+      await ctx.program.methods
+        .commitRelayed(Array.from(job_hash))
+        .accounts({
+          relayer: relayer.publicKey,
+          user: ctx.user.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          order: orderPda,
+          userTokenAccount: ctx.userTokenAccount,
+          relayerTokenAccount,
+          orderVaultTokenAccount,
+          relayAuthority: relayAuthorityPda(ctx),
+          bond: bondAccounts(ctx).bondPda,
+          mint: ctx.mint,
+          config: ctx.configPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction()
+    );
+  // Only the relayer signs the transaction
+  await sendAndConfirmTransaction(ctx.connection, tx, [relayer]);
+  return orderPda;
+}
+
+export async function relayedCommitSuccess(ctx: TestContext) {
+  const { relayer, relayerTokenAccount } = await fundRelayer(ctx);
+  await approveRelay(ctx);
+  const { amount: userBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+
+  const orderPda = await relayCommit(ctx, "relayedCommitSuccess", relayer, relayerTokenAccount);
+
+  const order = await ctx.program.account.order.fetch(orderPda);
+  assert.isDefined(order.status.started);
+  assert.equal(order.payer.toBase58(), ctx.user.publicKey.toBase58());
+
+  const { amount: userAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(userBefore - userAfter, ctx.price + RELAYER_FEE);
+  const { amount: relayerBalance } = await getAccount(ctx.connection, relayerTokenAccount);
+  assert.equal(relayerBalance, RELAYER_FEE);
+}
+
+// A token account has one delegate: the relay approval and a delegated
+// subscription on the same account never replace each other
+export async function relayAndSubscriptionShareAccount(ctx: TestContext) {
+  const { relayer, relayerTokenAccount } = await fundRelayer(ctx);
+  await mintTo(ctx.connection, ctx.user.payer, ctx.mint, ctx.userTokenAccount, ctx.user.payer, BigInt(2) * ctx.price);
+  const relayAuthority = relayAuthorityPda(ctx);
+
+  // A pending relay approval blocks a delegated subscription
+  await approveRelay(ctx);
+  try {
+    await createSubscription(ctx, 10, ONE_DAY_IN_SECONDS, { delegated: {} });
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.include(error.toString(), "TokenAccountDelegated");
+  }
+  let account = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(account.delegate.toBase58(), relayAuthority.toBase58());
+
+  // The relayed commit spends the whole approval, which frees the account
+  await relayCommit(ctx, "relayBeforeSubscription", relayer, relayerTokenAccount);
+  account = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.isNull(account.delegate);
+
+  const { subscriptionPda, subscriptionVaultTokenAccount } =
+    await createSubscription(ctx, 10, ONE_DAY_IN_SECONDS, { delegated: {} });
+
+  // The subscription's allowance cannot pay for a relayed commit
+  try {
+    await relayCommit(ctx, "relayDuringSubscription", relayer, relayerTokenAccount);
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.include(error.toString(), "TokenAccountDelegated");
+  }
+
+  // Cancelling keeps only the running period approved
+  await cancelSubscription(ctx, subscriptionPda, subscriptionVaultTokenAccount);
+  account = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(account.delegate.toBase58(), subscriptionPda.toBase58());
+  assert.equal(account.delegatedAmount, PRICE_PER_PERIOD);
+}
@@ -0,0 +1,34 @@
+import { assert } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { TestContext } from "../setup";
+
+export function getReputationView(ctx: TestContext, provider: PublicKey) {
+  return ctx.program.methods
+    .getReputation()
+    .accounts({ provider, config: ctx.configPda })
+    .view();
+}
+
+export async function reputationViewMatchesAccount(ctx: TestContext) {
+  const view = await getReputationView(ctx, ctx.service.publicKey);
+  const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+
+  assert.ok(view.provider.equals(ctx.service.publicKey));
+  assert.equal(view.ordersCompleted.toNumber(), reputation.ordersCompleted.toNumber());
+  assert.equal(view.ordersRefunded.toNumber(), reputation.ordersRefunded.toNumber());
+  assert.equal(view.weightedRatingsCount.toNumber(), reputation.weightedRatingsCount.toNumber());
+  assert.equal(view.volume.toNumber(), reputation.volume.toNumber());
+  const finished = reputation.ordersCompleted
+    .add(reputation.ordersAborted)
+    .add(reputation.ordersRefunded);
+  assert.equal(view.completionRateBps, reputation.ordersCompleted.muln(10_000).div(finished).toNumber());
+}
+
+export async function reputationViewOfUnknownProvider(ctx: TestContext) {
+  const provider = Keypair.generate().publicKey;
+  const view = await getReputationView(ctx, provider);
+
+  assert.ok(view.provider.equals(provider));
+  assert.equal(view.ordersCompleted.toNumber(), 0);
+  assert.equal(view.score, 0);
+}
@@ -0,0 +1,92 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import Keccak from 'keccak';
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getAcceptDeliveryTransaction, getDeliverTransaction } from "../helpers/deliver";
+import { getUpdateConfigTransaction } from "../helpers/config";
+
+function getRequestRevisionTransaction(ctx: TestContext) {
+  return ctx.program.methods
+    .requestRevision()
+    .accounts({
+      user: ctx.user.publicKey,
+      order: ctx.orderPda,
+      config: ctx.configPda,
+    })
+    .signers([ctx.user.payer]);
+}
+
+export async function revisionThenAccept(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, {
+    challengeWindow: new anchor.BN(ONE_DAY_IN_SECONDS),
+    maxRevisions: 1,
+    revisionPeriod: new anchor.BN(ONE_DAY_IN_SECONDS),
+  }).rpc();
+  try {
+    const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+    const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "revisionThenAccept", deadline);
+    const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+    await getDeliverTransaction(orderCtx, ctx.service).rpc();
+
+    await getRequestRevisionTransaction(orderCtx).rpc();
+    let order = await ctx.program.account.order.fetch(orderPda);
+    assert.isDefined(order.status.revision);
+    assert.equal(order.revisions, 1);
+
+    const revisedHash = Keccak('keccak256').update("revised result").digest();
+    await getDeliverTransaction({ ...orderCtx, resultHash: revisedHash }, ctx.service).rpc();
+    order = await ctx.program.account.order.fetch(orderPda);
+    assert.isDefined(order.status.delivered);
+    assert.deepEqual(Buffer.from(order.resultHash), revisedHash);
+
+    // Only one round is configured
+    try {
+      await getRequestRevisionTransaction(orderCtx).rpc();
+      assert.fail("Should have failed");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      assert.strictEqual(err.error.errorCode.code, "RevisionLimitReached");
+    }
+
+    const reputationBefore = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    await getAcceptDeliveryTransaction(orderCtx, null).rpc();
+    const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    assert.equal(reputation.revisions.toNumber(), reputationBefore.revisions.toNumber() + 1);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+// Asked for changes after an on-time delivery, the provider may redeliver past
+// the original deadline without the order counting as late
+export async function revisionPastDeadlineNotLate(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, {
+    maxRevisions: 1,
+    revisionPeriod: new anchor.BN(ONE_DAY_IN_SECONDS),
+  }).rpc();
+  try {
+    const deadline = BigInt(Math.floor(Date.now() / 1000) + 3);
+    const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "revisionPastDeadlineNotLate", deadline);
+    const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+    await getDeliverTransaction(orderCtx, ctx.service).rpc();
+    const delivered = await ctx.program.account.order.fetch(orderPda);
+
+    await getRequestRevisionTransaction(orderCtx).rpc();
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+    const revisedHash = Keccak('keccak256').update("late revision").digest();
+    await getDeliverTransaction({ ...orderCtx, resultHash: revisedHash }, ctx.service).rpc();
+
+    const order = await ctx.program.account.order.fetch(orderPda);
+    assert.equal(order.completedAt.toNumber(), delivered.completedAt.toNumber());
+
+    const reputationBefore = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    await getAcceptDeliveryTransaction(orderCtx, null).rpc();
+    const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    assert.equal(reputation.ordersCompleted.toNumber(), reputationBefore.ordersCompleted.toNumber() + 1);
+    assert.equal(reputation.ordersLate.toNumber(), reputationBefore.ordersLate.toNumber());
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
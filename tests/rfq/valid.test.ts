@@ -0,0 +1,103 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import Keccak from 'keccak';
+import { TestContext } from "../setup";
+import { bondAccounts } from "../helpers/bond";
+import { ONE_DAY_IN_SECONDS } from "../helpers/order";
+
+export function rfqAccounts(ctx: TestContext, job_name: string) {
+  const jobHash = Keccak('keccak256').update(job_name).digest();
+  const [orderPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("order"), ctx.user.publicKey.toBuffer(), jobHash],
+    ctx.program.programId
+  );
+  const [orderVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), orderPda.toBuffer()],
+    ctx.program.programId
+  );
+  return { jobHash, orderPda, orderVaultTokenAccount };
+}
+
+export async function estimate(ctx: TestContext, jobHash: Buffer, orderPda: PublicKey, budget: bigint) {
+  await ctx.program.methods
+    .estimate(Array.from(jobHash), new anchor.BN(budget.toString()))
+    .accounts({
+      user: ctx.user.publicKey,
+      order: orderPda,
+      mint: ctx.mint,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+}
+
+export function evaluate(ctx: TestContext, orderPda: PublicKey, price: bigint) {
+  const now = Math.floor(Date.now() / 1000);
+  return ctx.program.methods
+    .evaluate(
+      new anchor.BN(price.toString()),
+      new anchor.BN(now + ONE_DAY_IN_SECONDS),
+      new anchor.BN(now + 2 * ONE_DAY_IN_SECONDS)
+    )
+    .accounts({
+      authority: ctx.service.publicKey,
+      order: orderPda,
+      config: ctx.configPda,
+    })
+    .signers([ctx.service]);
+}
+
+export async function rfqSuccess(ctx: TestContext) {
+  const { jobHash, orderPda, orderVaultTokenAccount } = rfqAccounts(ctx, "rfqSuccess");
+  await estimate(ctx, jobHash, orderPda, ctx.price);
+
+  let order = await ctx.program.account.order.fetch(orderPda);
+  assert.isDefined(order.status.requested);
+  assert.equal(order.budget.toString(), ctx.price.toString());
+
+  await evaluate(ctx, orderPda, ctx.price).rpc();
+  order = await ctx.program.account.order.fetch(orderPda);
+  assert.isDefined(order.status.evaluated);
+
+  const { amount: amountBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  await ctx.program.methods
+    .commitEvaluation()
+    .accounts({
+      user: ctx.user.publicKey,
+      order: orderPda,
+      userTokenAccount: ctx.userTokenAccount,
+      orderVaultTokenAccount,
+      bond: bondAccounts(ctx).bondPda,
+      config: ctx.configPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  order = await ctx.program.account.order.fetch(orderPda);
+  assert.isDefined(order.status.started);
+
+  const vaultAccount = await getAccount(ctx.connection, orderVaultTokenAccount);
+  assert.equal(vaultAccount.amount, ctx.price);
+  const { amount: amountAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(amountBefore - amountAfter, ctx.price);
+}
+
+export async function rfqOverBudget(ctx: TestContext) {
+  const { jobHash, orderPda } = rfqAccounts(ctx, "rfqOverBudget");
+  await estimate(ctx, jobHash, orderPda, ctx.price);
+
+  try {
+    await evaluate(ctx, orderPda, ctx.price + BigInt(1)).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "BudgetExceeded");
+  }
+}
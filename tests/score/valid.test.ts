@@ -0,0 +1,69 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getAcceptDeliveryTransaction, getDeliverTransaction } from "../helpers/deliver";
+import { DEFAULT_CONFIG_PARAMS, getUpdateConfigTransaction } from "../helpers/config";
+import { getReputationView } from "../reputation/valid.test";
+
+// Without decay and with only the completion weight set, the score is the
+// plain completion rate over the provider's history
+export async function scoreTracksCompletionRate(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, {
+    score: { ...DEFAULT_CONFIG_PARAMS.score, completionWeight: 1 },
+  }).rpc();
+
+  try {
+    const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+    const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "scoreCompletion", deadline);
+    const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+    await getDeliverTransaction(orderCtx, ctx.service).rpc();
+    await getAcceptDeliveryTransaction(orderCtx, 5).rpc();
+
+    const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    const finished = reputation.decayedCompleted
+      .add(reputation.decayedAborted)
+      .add(reputation.decayedRefunded);
+    const expected = reputation.decayedCompleted.muln(10_000).div(finished).toNumber();
+    assert.equal(reputation.score, expected);
+
+    const view = await getReputationView(ctx, ctx.service.publicKey);
+    assert.equal(view.score, expected);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
+
+// The score read back later has aged by the half-life although nothing was
+// recorded in between. Volume is the only component, so decay shows directly.
+export async function scoreDecaysOverTime(ctx: TestContext) {
+  await getUpdateConfigTransaction(ctx, {
+    score: {
+      ...DEFAULT_CONFIG_PARAMS.score,
+      halfLife: new anchor.BN(2),
+      volumeWeight: 1,
+      volumeTarget: new anchor.BN((ctx.price * BigInt(2)).toString()),
+    },
+  }).rpc();
+
+  try {
+    const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+    const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "scoreDecay", deadline);
+    const orderCtx = { ...ctx, orderPda, orderVaultTokenAccount };
+    await getDeliverTransaction(orderCtx, ctx.service).rpc();
+    await getAcceptDeliveryTransaction(orderCtx, null).rpc();
+
+    const before = await getReputationView(ctx, ctx.service.publicKey);
+    assert.isAbove(before.score, 0);
+
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    const after = await getReputationView(ctx, ctx.service.publicKey);
+    assert.isBelow(after.score, before.score);
+    // Reading the score does not write the decayed history back
+    const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+    assert.isAbove(reputation.score, after.score);
+  } finally {
+    await getUpdateConfigTransaction(ctx).rpc();
+  }
+}
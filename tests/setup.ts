@@ -22,7 +22,10 @@ export interface TestContext {
     orderVaultTokenAccount: PublicKey;
     vaultTokenAccount: PublicKey;
     recipientTokenAccount: PublicKey;
+    reputationPda: PublicKey;
     provider: anchor.AnchorProvider;
+    // Orders are placed for `user` unless set
+    beneficiary?: PublicKey;
 }
 
 export async function setup(opts: { service?: Keypair, run_initialize?: boolean, jobString?: string } = { run_initialize: true }): Promise<TestContext> {
@@ -52,6 +55,7 @@ export async function setup(opts: { service?: Keypair, run_initialize?: boolean,
   let configPda: PublicKey;
   let orderVaultTokenAccount: PublicKey;
   let recipientTokenAccount: PublicKey;
+  let reputationPda: PublicKey;
     await connection.requestAirdrop(service.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
     mint = await createMint(
       connection,
@@ -127,7 +131,12 @@ export async function setup(opts: { service?: Keypair, run_initialize?: boolean,
     recipientTokenAccount = recipientAta.address;
 
     [vaultTokenAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), mint.toBuffer()],
+      [Buffer.from("vault"), service.publicKey.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+
+    [reputationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation"), service.publicKey.toBuffer()],
       program.programId
     );
 
@@ -148,5 +157,6 @@ export async function setup(opts: { service?: Keypair, run_initialize?: boolean,
         orderVaultTokenAccount,
         vaultTokenAccount,
         recipientTokenAccount,
+        reputationPda,
     }
 }
\ No newline at end of file
@@ -0,0 +1,124 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import Keccak from 'keccak';
+import { TestContext } from "../setup";
+
+export const PRICE_PER_PERIOD = BigInt(100_000);
+
+function subscriptionAccounts(ctx: TestContext, subscriptionId: number) {
+  const [subscriptionPda] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("subscription"),
+      ctx.user.publicKey.toBuffer(),
+      ctx.service.publicKey.toBuffer(),
+      new anchor.BN(subscriptionId).toArrayLike(Buffer, "le", 8),
+    ],
+    ctx.program.programId
+  );
+  const [subscriptionVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("subscription-vault"), subscriptionPda.toBuffer()],
+    ctx.program.programId
+  );
+  return { subscriptionPda, subscriptionVaultTokenAccount };
+}
+
+export async function createSubscription(ctx: TestContext, subscriptionId: number, period: number, funding: any) {
+  const accounts = subscriptionAccounts(ctx, subscriptionId);
+  await ctx.program.methods
+    .createSubscription(
+      new anchor.BN(subscriptionId),
+      new anchor.BN(period),
+      new anchor.BN(PRICE_PER_PERIOD.toString()),
+      3,
+      funding
+    )
+    .accounts({
+      client: ctx.user.publicKey,
+      provider: ctx.service.publicKey,
+      subscription: accounts.subscriptionPda,
+      subscriptionVaultTokenAccount: accounts.subscriptionVaultTokenAccount,
+      clientTokenAccount: ctx.userTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+  return accounts;
+}
+
+async function charge(ctx: TestContext, subscriptionPda: PublicKey, subscriptionVaultTokenAccount: PublicKey) {
+  const resultHash = Keccak('keccak256').update("period result").digest();
+  await ctx.program.methods
+    .charge(Array.from(resultHash))
+    .accounts({
+      authority: ctx.service.publicKey,
+      subscription: subscriptionPda,
+      subscriptionVaultTokenAccount,
+      clientTokenAccount: ctx.userTokenAccount,
+      vaultAuthority: ctx.vaultAuthority,
+      vaultTokenAccount: ctx.vaultTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.service])
+    .rpc();
+}
+
+export async function cancelSubscription(ctx: TestContext, subscriptionPda: PublicKey, subscriptionVaultTokenAccount: PublicKey) {
+  await ctx.program.methods
+    .cancelSubscription()
+    .accounts({
+      client: ctx.user.publicKey,
+      subscription: subscriptionPda,
+      subscriptionVaultTokenAccount,
+      clientTokenAccount: ctx.userTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+}
+
+export async function chargePrefunded(ctx: TestContext) {
+  const { subscriptionPda, subscriptionVaultTokenAccount } = await createSubscription(ctx, 1, 1, { prefunded: {} });
+  const vaultBefore = (await getAccount(ctx.connection, subscriptionVaultTokenAccount)).amount;
+  assert.equal(vaultBefore, PRICE_PER_PERIOD * BigInt(3));
+
+  await new Promise((resolve) => setTimeout(resolve, 2000));
+  await charge(ctx, subscriptionPda, subscriptionVaultTokenAccount);
+
+  const subscription = await ctx.program.account.subscription.fetch(subscriptionPda);
+  assert.equal(subscription.periodsCharged, 1);
+  const vaultAfter = (await getAccount(ctx.connection, subscriptionVaultTokenAccount)).amount;
+  assert.equal(vaultBefore - vaultAfter, PRICE_PER_PERIOD);
+}
+
+export async function chargeDelegated(ctx: TestContext) {
+  const { subscriptionPda, subscriptionVaultTokenAccount } = await createSubscription(ctx, 2, 1, { delegated: {} });
+  const { amount: amountBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+
+  await new Promise((resolve) => setTimeout(resolve, 2000));
+  await charge(ctx, subscriptionPda, subscriptionVaultTokenAccount);
+
+  const { amount: amountAfter, delegate } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(amountBefore - amountAfter, PRICE_PER_PERIOD);
+  assert.equal(delegate.toBase58(), subscriptionPda.toBase58());
+}
+
+export async function cancelRefundsFuturePeriods(ctx: TestContext) {
+  const { subscriptionPda, subscriptionVaultTokenAccount } = await createSubscription(ctx, 3, 60 * 60, { prefunded: {} });
+  const { amount: amountBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+
+  await cancelSubscription(ctx, subscriptionPda, subscriptionVaultTokenAccount);
+
+  // The running period stays with the provider, the other two come back
+  const subscription = await ctx.program.account.subscription.fetch(subscriptionPda);
+  assert.isDefined(subscription.status.cancelled);
+  assert.equal(subscription.periods, 1);
+  const { amount: amountAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(amountAfter - amountBefore, PRICE_PER_PERIOD * BigInt(2));
+}
@@ -0,0 +1,63 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { TestContext } from "../setup";
+import { commitOrder, ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { getDeliverTransaction, getFinalizeTransaction } from "../helpers/deliver";
+
+const TIP = BigInt(25_000);
+
+function getTipTransaction(ctx: TestContext, amount: bigint) {
+  return ctx.program.methods
+    .tip(new anchor.BN(amount.toString()))
+    .accounts({
+      user: ctx.user.publicKey,
+      order: ctx.orderPda,
+      userTokenAccount: ctx.userTokenAccount,
+      vaultAuthority: ctx.vaultAuthority,
+      vaultTokenAccount: ctx.vaultTokenAccount,
+      reputation: ctx.reputationPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer]);
+}
+
+async function commitOpenOrder(ctx: TestContext, job_name: string): Promise<TestContext> {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + ONE_DAY_IN_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, job_name, deadline);
+  return { ...ctx, orderPda, orderVaultTokenAccount };
+}
+
+export async function tipSuccess(ctx: TestContext) {
+  const orderCtx = await commitOpenOrder(ctx, "tipSuccess");
+  await getDeliverTransaction(orderCtx, ctx.service).rpc();
+  await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+
+  const { amount: vaultBefore } = await getAccount(ctx.connection, ctx.vaultTokenAccount);
+  const reputationBefore = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+
+  await getTipTransaction(orderCtx, TIP).rpc();
+
+  const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.equal(order.tipped.toString(), TIP.toString());
+  const { amount: vaultAfter } = await getAccount(ctx.connection, ctx.vaultTokenAccount);
+  assert.equal(vaultAfter - vaultBefore, TIP);
+
+  const reputation = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+  assert.equal(reputation.tipsCount.toNumber(), reputationBefore.tipsCount.toNumber() + 1);
+}
+
+export async function tipBeforeCompletion(ctx: TestContext) {
+  const orderCtx = await commitOpenOrder(ctx, "tipBeforeCompletion");
+
+  try {
+    await getTipTransaction(orderCtx, TIP).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "InvalidOrderStatus");
+  }
+}
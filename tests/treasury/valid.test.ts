@@ -0,0 +1,131 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { createAccount, getAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import Keccak from 'keccak';
+import { Treasury } from "../../target/types/treasury";
+import { TestContext } from "../setup";
+import { bondAccounts } from "../helpers/bond";
+import { buildOrderMessage, prepareAndSubmitTransaction, signEd25519 } from "../helpers/commit";
+import { ONE_DAY_IN_SECONDS } from "../helpers/order";
+import { depositBond } from "../bond/valid.test";
+
+// The treasury program places an order as a PDA through CPI and gets the
+// refund back into a token account that is not the treasury's ATA
+export async function treasuryOrderRefunded(ctx: TestContext) {
+  await placeAndRefund(ctx, "treasuryOrder", false);
+}
+
+// With a bonded provider the treasury passes the bond through to the refund
+export async function treasuryBondedOrderRefunded(ctx: TestContext) {
+  await depositBond(ctx, ctx.price);
+  await placeAndRefund(ctx, "treasuryBondedOrder", true);
+
+  const bond = await ctx.program.account.bond.fetch(bondAccounts(ctx).bondPda);
+  assert.equal(bond.outstanding.toNumber(), 0);
+}
+
+async function placeAndRefund(ctx: TestContext, job_name: string, bonded: boolean) {
+  const treasuryProgram = anchor.workspace.treasury as Program<Treasury>;
+  const [treasury] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury"), ctx.user.publicKey.toBuffer()],
+    treasuryProgram.programId
+  );
+  const [clientStats] = PublicKey.findProgramAddressSync(
+    [Buffer.from("client-stats"), treasury.toBuffer()],
+    ctx.program.programId
+  );
+
+  // Rent for the order accounts comes out of the treasury
+  await ctx.provider.sendAndConfirm(
+    new anchor.web3.Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: ctx.user.publicKey,
+        toPubkey: treasury,
+        lamports: anchor.web3.LAMPORTS_PER_SOL,
+      })
+    )
+  );
+  const treasuryTokenAccount = await createAccount(
+    ctx.connection,
+    ctx.user.payer,
+    ctx.mint,
+    treasury,
+    Keypair.generate()
+  );
+  await mintTo(ctx.connection, ctx.user.payer, ctx.mint, treasuryTokenAccount, ctx.user.payer, ctx.price);
+
+  const jobHash = Keccak('keccak256').update(job_name).digest();
+  const [orderPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("order"), treasury.toBuffer(), jobHash],
+    ctx.program.programId
+  );
+  const [orderVaultTokenAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), orderPda.toBuffer()],
+    ctx.program.programId
+  );
+
+  // Already past the deadline so the refund is available right away
+  const message = await buildOrderMessage(ctx, {
+    user: treasury.toBytes(),
+    job_hash: jobHash,
+    deadline: BigInt(Math.floor(Date.now() / 1000) - ONE_DAY_IN_SECONDS),
+  });
+  const signature = await signEd25519(message, ctx.service.secretKey);
+  const tx = new anchor.web3.Transaction()
+    .add(
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: ctx.service.publicKey.toBytes(),
+        message,
+        signature,
+      })
+    )
+    .add(
+      await treasuryProgram.methods
+        .placeOrder(Array.from(jobHash))
+        .accounts({
+          authority: ctx.user.publicKey,
+          treasuryTokenAccount,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          order: orderPda,
+          orderVaultTokenAccount,
+          clientStats,
+          config: ctx.configPda,
+          bond: bondAccounts(ctx).bondPda,
+          mint: ctx.mint,
+          gatewayProgram: ctx.program.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .instruction()
+    );
+  await prepareAndSubmitTransaction(ctx, tx);
+
+  const order = await ctx.program.account.order.fetch(orderPda);
+  assert.equal(order.bonded, bonded);
+  assert.ok(order.user.equals(treasury));
+  assert.ok(order.payer.equals(treasury));
+  assert.equal((await getAccount(ctx.connection, treasuryTokenAccount)).amount, BigInt(0));
+
+  await treasuryProgram.methods
+    .refundOrder()
+    .accounts({
+      authority: ctx.user.publicKey,
+      treasuryTokenAccount,
+      order: orderPda,
+      orderVaultTokenAccount,
+      reputation: ctx.reputationPda,
+      clientStats,
+      bond: bonded ? bondAccounts(ctx).bondPda : null,
+      bondVaultTokenAccount: bonded ? bondAccounts(ctx).bondVaultTokenAccount : null,
+      config: ctx.configPda,
+      mint: ctx.mint,
+      gatewayProgram: ctx.program.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+  const refunded = await ctx.program.account.order.fetch(orderPda);
+  assert.isDefined(refunded.status.refunded);
+  assert.equal((await getAccount(ctx.connection, treasuryTokenAccount)).amount, ctx.price);
+}
@@ -2,21 +2,41 @@
 import { Schema, serialize } from "borsh";
 import bs58 from "bs58";
 
+export const OFFER_VERSION = 1;
+
 export class Order {
+    version: number;
     user: Uint8Array;
     job_hash: Uint8Array;
     price: bigint;
     mint: Uint8Array;
     price_valid_until: bigint;
     deadline: bigint;
+    vesting: boolean;
+    grace_period: bigint;
+    penalty_bps_per_hour: number;
+    bonus: bigint;
+    bonus_deadline: bigint;
+    relayer_fee: bigint;
+    client_deposit: bigint;
+    max_client_unsatisfied_bps: number;
   
     constructor(fields: {
+      version: number;
       user: Uint8Array;
       job_hash: Uint8Array;
       price: bigint;
       mint: Uint8Array;
       price_valid_until: bigint;
       deadline: bigint;
+      vesting: boolean;
+      grace_period: bigint;
+      penalty_bps_per_hour: number;
+      bonus: bigint;
+      bonus_deadline: bigint;
+      relayer_fee: bigint;
+      client_deposit: bigint;
+      max_client_unsatisfied_bps: number;
     }) {
       Object.assign(this, fields);
     }
@@ -24,6 +44,7 @@ export class Order {
   
   const schema: Schema = {
     struct: {
+      version: "u8",
       user: {
         array: {
           type: "u8",
@@ -45,6 +66,14 @@ export class Order {
       },
       price_valid_until: "u64",
       deadline: "i64",
+      vesting: "bool",
+      grace_period: "i64",
+      penalty_bps_per_hour: "u16",
+      bonus: "u64",
+      bonus_deadline: "i64",
+      relayer_fee: "u64",
+      client_deposit: "u64",
+      max_client_unsatisfied_bps: "u16",
     }
   };
   
@@ -53,4 +82,100 @@ export class Order {
     //const serializedOrder = serialize(schema, order);
     //return bs58.encode(serializedOrder);
     return serialize(schema, order);
-  }
\ No newline at end of file
+  }
+
+export class UsageReceipt {
+    credit: Uint8Array;
+    cumulative_amount: bigint;
+    counter: bigint;
+
+    constructor(fields: {
+      credit: Uint8Array;
+      cumulative_amount: bigint;
+      counter: bigint;
+    }) {
+      Object.assign(this, fields);
+    }
+  }
+
+  const usageReceiptSchema: Schema = {
+    struct: {
+      credit: {
+        array: {
+          type: "u8",
+          len: 32
+        }
+      },
+      cumulative_amount: "u64",
+      counter: "u64",
+    }
+  };
+
+  export const serializeUsageReceipt = (receipt: UsageReceipt) => {
+    return serialize(usageReceiptSchema, receipt);
+  }
+
+export class Voucher {
+    channel: Uint8Array;
+    amount: bigint;
+
+    constructor(fields: {
+      channel: Uint8Array;
+      amount: bigint;
+    }) {
+      Object.assign(this, fields);
+    }
+  }
+
+  const voucherSchema: Schema = {
+    struct: {
+      channel: {
+        array: {
+          type: "u8",
+          len: 32
+        }
+      },
+      amount: "u64",
+    }
+  };
+
+  export const serializeVoucher = (voucher: Voucher) => {
+    return serialize(voucherSchema, voucher);
+  }
+
+export class Amendment {
+    order: Uint8Array;
+    amendment: number;
+    price: bigint;
+    deadline: bigint | null;
+    price_valid_until: bigint;
+
+    constructor(fields: {
+      order: Uint8Array;
+      amendment: number;
+      price: bigint;
+      deadline: bigint | null;
+      price_valid_until: bigint;
+    }) {
+      Object.assign(this, fields);
+    }
+  }
+
+  const amendmentSchema: Schema = {
+    struct: {
+      order: {
+        array: {
+          type: "u8",
+          len: 32
+        }
+      },
+      amendment: "u32",
+      price: "u64",
+      deadline: { option: "i64" },
+      price_valid_until: "u64",
+    }
+  };
+
+  export const serializeAmendment = (amendment: Amendment) => {
+    return serialize(amendmentSchema, amendment);
+  }
@@ -0,0 +1,191 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { createHash, randomBytes } from "crypto";
+import Keccak from 'keccak';
+import { TestContext } from "../setup";
+import { commitOrder } from "../helpers/order";
+import { getFinalizeTransaction } from "../helpers/deliver";
+
+const VESTING_DURATION_SECONDS = 8;
+
+function sleep(ms: number) {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+async function commitVestingOrder(
+  ctx: TestContext,
+  job_name: string,
+  duration: number = VESTING_DURATION_SECONDS
+): Promise<TestContext> {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + duration);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, job_name, deadline, { vesting: true });
+  return { ...ctx, orderPda, orderVaultTokenAccount };
+}
+
+function getClaimVestedTransaction(ctx: TestContext) {
+  return ctx.program.methods
+    .claimVested()
+    .accounts({
+      authority: ctx.service.publicKey,
+      order: ctx.orderPda,
+      orderVaultTokenAccount: ctx.orderVaultTokenAccount,
+      vaultTokenAccount: ctx.vaultTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.service]);
+}
+
+function getStopVestingTransaction(ctx: TestContext) {
+  return ctx.program.methods
+    .stopVesting()
+    .accounts({
+      user: ctx.user.publicKey,
+      payer: ctx.user.publicKey,
+      order: ctx.orderPda,
+      payerTokenAccount: ctx.userTokenAccount,
+      orderVaultTokenAccount: ctx.orderVaultTokenAccount,
+      vaultTokenAccount: ctx.vaultTokenAccount,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.user.payer]);
+}
+
+export async function claimVestedThenStop(ctx: TestContext) {
+  const { amount: userBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  const orderCtx = await commitVestingOrder(ctx, "claimVestedThenStop");
+
+  await sleep(3000);
+  await getClaimVestedTransaction(orderCtx).rpc();
+
+  const claimed = (await ctx.program.account.order.fetch(orderCtx.orderPda)).vestedClaimed;
+  assert.isTrue(claimed.gtn(0), "Part of the price should have vested");
+  assert.isTrue(claimed.lt(new anchor.BN(ctx.price.toString())), "Not all of the price should have vested");
+  const { amount: vaultAfterClaim } = await getAccount(ctx.connection, ctx.vaultTokenAccount);
+  assert.equal(vaultAfterClaim.toString(), claimed.toString());
+
+  await getStopVestingTransaction(orderCtx).rpc();
+
+  const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.isDefined(order.status.aborted);
+
+  // The provider keeps everything vested, the client gets the rest back
+  const { amount: vaultAfterStop } = await getAccount(ctx.connection, ctx.vaultTokenAccount);
+  const { amount: userAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(vaultAfterStop.toString(), order.vestedClaimed.toString());
+  assert.equal(userAfter, userBefore - BigInt(order.vestedClaimed.toString()));
+
+  try {
+    await getAccount(ctx.connection, orderCtx.orderVaultTokenAccount);
+    assert.fail("Order vault token account should be closed");
+  } catch (error) {
+    assert.equal(error.name, "TokenAccountNotFoundError");
+  }
+}
+
+export async function vestingCompletesWhenFullyVested(ctx: TestContext) {
+  const { amount: vaultBefore } = await getAccount(ctx.connection, ctx.vaultTokenAccount);
+  const orderCtx = await commitVestingOrder(ctx, "vestingCompletesWhenFullyVested");
+
+  try {
+    await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "VestingInProgress");
+  }
+
+  await sleep((VESTING_DURATION_SECONDS + 1) * 1000);
+
+  // Past the deadline the client can no longer stop the order
+  try {
+    await getStopVestingTransaction(orderCtx).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "VestingComplete");
+  }
+
+  await getClaimVestedTransaction(orderCtx).rpc();
+  const repBefore = await ctx.program.account.reputation.fetchNullable(ctx.reputationPda);
+  await getFinalizeTransaction(orderCtx, ctx.service).rpc();
+
+  const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.isDefined(order.status.completed);
+
+  const { amount: vaultAfter } = await getAccount(ctx.connection, ctx.vaultTokenAccount);
+  assert.equal(vaultAfter - vaultBefore, ctx.price);
+
+  const rep = await ctx.program.account.reputation.fetch(ctx.reputationPda);
+  const completedBefore = repBefore ? repBefore.ordersCompleted.toNumber() : 0;
+  const lateBefore = repBefore ? repBefore.ordersLate.toNumber() : 0;
+  assert.equal(rep.ordersCompleted.toNumber(), completedBefore + 1);
+  assert.equal(rep.ordersLate.toNumber(), lateBefore, "A vesting order is never late");
+
+  try {
+    await getAccount(ctx.connection, orderCtx.orderVaultTokenAccount);
+    assert.fail("Order vault token account should be closed");
+  } catch (error) {
+    assert.equal(error.name, "TokenAccountNotFoundError");
+  }
+}
+
+// A vesting order that took an encrypted delivery no longer streams, so a
+// provider withholding the key must not leave the escrow stuck
+export async function vestingEncryptedRefundedWhenKeyWithheld(ctx: TestContext) {
+  const { amount: userBefore } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  const orderCtx = await commitVestingOrder(ctx, "vestingEncryptedRefundedWhenKeyWithheld", 4);
+
+  const ciphertextHash = Keccak('keccak256').update("encrypted result").digest();
+  const keyHash = createHash("sha256").update(randomBytes(32)).digest();
+  await ctx.program.methods
+    .deliverEncrypted(Array.from(ciphertextHash), Array.from(keyHash))
+    .accounts({
+      authority: ctx.service.publicKey,
+      order: orderCtx.orderPda,
+    })
+    .signers([ctx.service])
+    .rpc();
+
+  await sleep(5000);
+  await ctx.program.methods
+    .refund()
+    .accounts({
+      user: ctx.user.publicKey,
+      order: orderCtx.orderPda,
+      userTokenAccount: ctx.userTokenAccount,
+      orderVaultTokenAccount: orderCtx.orderVaultTokenAccount,
+      reputation: ctx.reputationPda,
+      mint: ctx.mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    })
+    .signers([ctx.user.payer])
+    .rpc();
+
+  const order = await ctx.program.account.order.fetch(orderCtx.orderPda);
+  assert.isDefined(order.status.refunded);
+  const { amount: userAfter } = await getAccount(ctx.connection, ctx.userTokenAccount);
+  assert.equal(userAfter, userBefore);
+}
+
+export async function claimNonVestingOrder(ctx: TestContext) {
+  const deadline = BigInt(Math.floor(Date.now() / 1000) + VESTING_DURATION_SECONDS);
+  const { orderPda, orderVaultTokenAccount } = await commitOrder(ctx, "claimNonVestingOrder", deadline);
+
+  try {
+    await getClaimVestedTransaction({ ...ctx, orderPda, orderVaultTokenAccount }).rpc();
+    assert.fail("Should have failed");
+  } catch (error) {
+    assert.isTrue(error instanceof anchor.AnchorError);
+    const err: anchor.AnchorError = error;
+    assert.strictEqual(err.error.errorCode.code, "NotVestingOrder");
+  }
+}
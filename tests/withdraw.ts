@@ -3,7 +3,6 @@ import { assert } from "chai";
 import { TestContext } from "./setup";
 import { buildWithdrawTransaction } from "./helpers/withdraw";
 import { AnchorError } from "@coral-xyz/anchor";
-import { Keypair, sendAndConfirmTransaction, SystemProgram, Transaction } from "@solana/web3.js";
 
 export async function withdraw(ctx: TestContext) {
     // Get initial balances
@@ -39,37 +38,21 @@ export async function withdrawWrongAuthority(ctx: TestContext) {
     } catch (error) {
         assert.isTrue(error instanceof AnchorError);
         const err: AnchorError = error;
-        assert.strictEqual(err.error.errorCode.code, "ConstraintHasOne");
-        assert.strictEqual(err.error.origin, "config");
+        assert.strictEqual(err.error.errorCode.code, "ConstraintSeeds");
+        assert.strictEqual(err.error.origin, "vault_token_account");
     }
 }
 
-export async function withdrawWrongConfig(ctx: TestContext) {
+export async function withdrawWrongVault(ctx: TestContext) {
     try {
-      const emptyDataAccount = Keypair.generate();
-      const space = 104;
-      const lamports = await ctx.connection.getMinimumBalanceForRentExemption(space);
-      const tx = new Transaction().add(
-        SystemProgram.createAccount({
-          fromPubkey: ctx.service.publicKey,
-          newAccountPubkey: emptyDataAccount.publicKey,
-          lamports,
-          space,
-          programId: ctx.program.programId
-        })
-      );
-      await sendAndConfirmTransaction(ctx.connection, tx, [
-        ctx.service,
-        emptyDataAccount
-      ]);
         await buildWithdrawTransaction(ctx, {
-            config: emptyDataAccount.publicKey
+            vaultTokenAccount: ctx.userTokenAccount
         }).rpc();
         assert.fail("Should have failed");
     } catch (error) {
         assert.isTrue(error instanceof AnchorError);
         const err: AnchorError = error;
-        assert.strictEqual(err.error.errorCode.code, "AccountDiscriminatorMismatch");
-        assert.strictEqual(err.error.origin, "config");
+        assert.strictEqual(err.error.errorCode.code, "ConstraintSeeds");
+        assert.strictEqual(err.error.origin, "vault_token_account");
     }
 }
\ No newline at end of file